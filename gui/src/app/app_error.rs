@@ -0,0 +1,98 @@
+use eframe::egui::{self, Context};
+use hg::simulation::{SimulationError, load_events};
+
+use super::{HGSimApp, AppState};
+
+// Error window
+impl HGSimApp {
+    pub(super) fn error_window(&mut self, ctx: &Context) {
+        let err = match self.error.take() {
+            Some(err) => err,
+            None => return
+        };
+
+        // the event that caused the error, if it was caused by
+        // an event that failed mid-game
+        let event_file = match &err {
+            SimulationError::EventError { file, .. } => Some(file.clone()),
+            _ => None
+        };
+
+        let mut resolved = false;
+
+        egui::Window::new("Simulation Error")
+          .collapsible(false)
+          .resizable(false)
+          .show(ctx, |ui| {
+              ui.label(format!("{err}"));
+
+              match &err {
+                  SimulationError::EventError { file, .. } |
+                  SimulationError::FileError { file, .. } => {
+                      ui.label(format!("File: {file}"));
+                  },
+                  _ => ()
+              }
+
+              ui.separator();
+
+              ui.horizontal(|ui| {
+                  if let Some(file) = &event_file {
+                      if ui.button("Skip Event").clicked() {
+                          if let Some(sim) = &mut self.simulation {
+                              sim.skip_event(file);
+                          }
+
+                          self.retry_step();
+                          resolved = true;
+                      }
+                  }
+
+                  if ui.button("Reload Data").clicked() {
+                      resolved = self.reload_data();
+                  }
+
+                  if ui.button("Main Menu").clicked() {
+                      self.app_state = AppState::MainMenu;
+                      resolved = true;
+                  }
+              });
+          });
+
+        // a failed reload puts its own error in place
+        if !resolved && self.error.is_none() {
+            self.error = Some(err);
+        }
+    }
+
+    // Reloads the event files, keeping the game (and its log) intact.
+    // If there's no game to keep, everything is reloaded from scratch
+    fn reload_data(&mut self) -> bool {
+        let sim = match &mut self.simulation {
+            Some(sim) => sim,
+            None => return self.rebuild_sim()
+        };
+
+        match load_events(&self.settings) {
+            Ok(events) => {
+                sim.reload_events(events);
+                self.retry_step();
+                true
+            },
+            Err(err) => {
+                self.error = Some(err);
+                false
+            }
+        }
+    }
+
+    // Tries the phase that failed again, if we were in the middle of one
+    fn retry_step(&mut self) {
+        if let AppState::Simulation { page, .. } = self.app_state {
+            self.app_state = AppState::Simulation {
+                page,
+                advance_step: true
+            };
+        }
+    }
+}
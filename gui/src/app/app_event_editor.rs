@@ -24,11 +24,20 @@ impl HGSimApp {
                     state: EditedState::Added,
                     event: hg::event::Event {
                         file_name: format!("event_{file_id}"),
+                        format: hg::data_trait::DataFormat::Toml,
                         text: String::new(),
                         killed: Vec::new(),
                         killers: Vec::new(),
+                        assists: Vec::new(),
+                        injured: Vec::new(),
+                        allies: Vec::new(),
+                        cause: String::new(),
                         category: EventCategory::Bloodbath,
-                        weight: 50
+                        weight: 50,
+                        script: String::new(),
+                        compiled_script: None,
+                        follow_ups: Vec::new(),
+                        outcomes: Vec::new()
                     },
                     id: rand.gen(),
                     killers: String::new(),
@@ -0,0 +1,116 @@
+use eframe::egui::{self, Context};
+use hg::import::{import_cast_csv, save_districts};
+
+use super::{HGSimApp, AppState, ImportState};
+
+// Import window
+impl HGSimApp {
+    pub(super) fn toggle_import(&mut self) {
+        if self.import.is_some() {
+            self.import = None;
+            return;
+        }
+
+        self.import = Some(ImportState {
+            csv_path: String::from("cast.csv"),
+            dir: String::from("imported/districts"),
+            imported_dir: None,
+            status: None,
+            problems: Vec::new(),
+        });
+    }
+
+    pub(super) fn import_window(&mut self, ctx: &Context) {
+        let mut import = match self.import.take() {
+            Some(import) => import,
+            None => return
+        };
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new("Import Cast")
+          .open(&mut open)
+          .collapsible(false)
+          .show(ctx, |ui| {
+              ui.label("A CSV file with name, district, pronouns and avatar columns");
+
+              ui.horizontal(|ui| {
+                  ui.label("CSV File ");
+                  ui.text_edit_singleline(&mut import.csv_path);
+              });
+
+              ui.horizontal(|ui| {
+                  ui.label("Save Districts To ");
+                  ui.text_edit_singleline(&mut import.dir);
+              });
+
+              ui.horizontal(|ui| {
+                  if ui.button("Import").clicked() {
+                      import_csv(&mut import);
+                  }
+
+                  if let Some(dir) = &import.imported_dir {
+                      if ui.button("Use This Cast").clicked() {
+                          self.settings.district_folders = vec![ dir.clone() ];
+                          // a made up cast would win over the imported one
+                          self.settings.random_cast = None;
+
+                          if let Err(err) = self.settings.save(&self.settings_path) {
+                              import.status = Some(format!("{err}"));
+                          } else {
+                              // any errors end up in the error window
+                              self.rebuild_sim();
+                              self.app_state = AppState::MainMenu;
+                              close = true;
+                          }
+                      }
+                  }
+              });
+
+              if let Some(status) = &import.status {
+                  ui.label(status.clone());
+              }
+
+              if !import.problems.is_empty() {
+                  ui.separator();
+                  ui.label(format!("{} line(s) could not be imported:", import.problems.len()));
+
+                  egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                      for problem in &import.problems {
+                          ui.label(problem.clone());
+                      }
+                  });
+              }
+          });
+
+        if open && !close {
+            self.import = Some(import);
+        }
+    }
+}
+
+fn import_csv(import: &mut ImportState) {
+    import.imported_dir = None;
+    import.problems.clear();
+
+    let contents = match std::fs::read_to_string(&import.csv_path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            import.status = Some(format!("Could not read {}: {}", import.csv_path, err));
+            return
+        }
+    };
+
+    let cast = import_cast_csv(&contents);
+    import.problems = cast.problems.iter().map(|problem| problem.to_string()).collect();
+
+    if let Err(err) = save_districts(&cast.districts, &import.dir) {
+        import.status = Some(format!("{err}"));
+        return
+    }
+
+    let tributes = cast.districts.iter().map(|dist| dist.tributes.len()).sum::<usize>();
+    import.status = Some(format!("Imported {} tributes in {} districts into {}", tributes, cast.districts.len(), import.dir));
+    import.imported_dir = Some(import.dir.clone());
+}
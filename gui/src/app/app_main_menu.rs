@@ -4,13 +4,39 @@ use super::{HGSimApp, AppState, EditedEvent, EditedState, EventEditorState};
 
 impl HGSimApp {
     pub(super) fn main_menu(&mut self, _ctx: &Context, ui: &mut Ui) {
+        let simulation = match &mut self.simulation {
+            Some(sim) => sim,
+            None => {
+                ui.vertical_centered(|ui| {
+                    ui.label("The simulation could not be loaded. Check your options and data files, then try again.");
+
+                    if ui.button("Reload Data").clicked() {
+                        self.rebuild_sim();
+                    }
+                });
+
+                return
+            }
+        };
+
+        let load_errors = simulation.get_load_errors();
+        if !load_errors.is_empty() {
+            ui.collapsing(format!("Skipped files because of {} error(s) while loading", load_errors.len()), |ui| {
+                for err in load_errors {
+                    ui.label(err);
+                }
+            });
+
+            ui.separator();
+        }
+
         ui.vertical_centered(|ui| {
             ui.label("(WIP!)");
             if ui.button("Edit Events").clicked() {
                 let mut events = Vec::new();
                 let mut rand = rand::thread_rng();
 
-                for event in self.simulation.get_all_events() {
+                for event in simulation.get_all_events() {
                     let killers = event.killers.join(", ");
                     let killed = event.killed.join(", ");
 
@@ -39,11 +65,36 @@ impl HGSimApp {
             
             ui.label("");
             
-            if ui.button("Run Simulation").clicked() {
+            let history_len = simulation.get_history().len();
+            let run_text = if history_len > 0 { "Continue Simulation" } else { "Run Simulation" };
+
+            if ui.button(run_text).clicked() {
                 self.app_state = AppState::Simulation {
-                    title: self.simulation.get_category_title(),
-                    events: Vec::new(),
-                    advance_step: true
+                    page: history_len.saturating_sub(1),
+                    advance_step: history_len == 0
+                }
+            }
+
+            if history_len > 0 {
+                ui.label("");
+
+                let mut seed = None;
+
+                if ui.button("New Game (Same Seed)").clicked() {
+                    seed = Some(Some(simulation.get_seed()));
+                }
+
+                if ui.button("New Game (New Seed)").clicked() {
+                    seed = Some(None);
+                }
+
+                if let Some(seed) = seed {
+                    simulation.reset(seed);
+
+                    self.app_state = AppState::Simulation {
+                        page: 0,
+                        advance_step: true
+                    }
                 }
             }
         });
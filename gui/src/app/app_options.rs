@@ -0,0 +1,211 @@
+use eframe::egui::{self, Context, Ui, RichText};
+use hg::{simulation_settings::{VictoryMode, KillCredit, MAX_SEED}, event_pack::EventPack, cast_generator::CastSettings};
+
+use super::{HGSimApp, AppState, OptionsState};
+
+// Options window
+impl HGSimApp {
+    pub(super) fn toggle_options(&mut self) {
+        if self.options.is_some() {
+            self.options = None;
+            return;
+        }
+
+        self.options = Some(OptionsState {
+            settings: self.settings.clone(),
+            random_seed: self.settings.seed.is_none(),
+            seed: match (self.settings.seed, &self.simulation) {
+                (Some(seed), _) => seed.to_string(),
+                (None, Some(sim)) => sim.get_seed().to_string(),
+                (None, None) => String::new()
+            },
+            status: None,
+        });
+    }
+
+    pub(super) fn options_window(&mut self, ctx: &Context) {
+        let mut options = match self.options.take() {
+            Some(options) => options,
+            None => return
+        };
+
+        let mut open = true;
+        let mut close = false;
+
+        egui::Window::new("Options")
+          .open(&mut open)
+          .collapsible(false)
+          .resizable(false)
+          .show(ctx, |ui| {
+              ui.label("Event Folders");
+              folder_list(ui, &mut options.settings.event_folders);
+
+              ui.separator();
+
+              ui.label("District Folders");
+              let mut random_cast = options.settings.random_cast.is_some();
+              if ui.checkbox(&mut random_cast, "Make up a random cast instead").changed() {
+                  options.settings.random_cast = random_cast.then(|| CastSettings::new(12, 2));
+              }
+
+              match &mut options.settings.random_cast {
+                  Some(cast) => {
+                      ui.horizontal(|ui| {
+                          ui.label("Districts ");
+                          ui.add(egui::DragValue::new(&mut cast.districts).clamp_range(1..=48));
+                          ui.label("Tributes Each ");
+                          ui.add(egui::DragValue::new(&mut cast.tributes_per_district).clamp_range(1..=12));
+                      });
+                  },
+                  None => folder_list(ui, &mut options.settings.district_folders)
+              }
+
+              ui.separator();
+
+              if let Some(sim) = &self.simulation {
+                  if !sim.get_packs().is_empty() {
+                      ui.label("Event Packs");
+                      pack_list(ui, sim.get_packs(), &mut options.settings.disabled_packs);
+
+                      ui.separator();
+                  }
+              }
+
+              ui.horizontal(|ui| {
+                  ui.label("Death Rate ");
+                  ui.add(egui::Slider::new(&mut options.settings.death_rate, 0.0..=1.0));
+              });
+
+              ui.horizontal(|ui| {
+                  ui.label("Seed ");
+                  ui.checkbox(&mut options.random_seed, "Random");
+                  ui.add_enabled(!options.random_seed, egui::TextEdit::singleline(&mut options.seed));
+              });
+
+              ui.horizontal(|ui| {
+                  ui.label("Victory Mode ");
+                  egui::ComboBox::from_id_source("victory_mode")
+                    .selected_text(format!("{:?}", options.settings.victory_mode))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut options.settings.victory_mode, VictoryMode::LastTribute, "Last Tribute");
+                        ui.selectable_value(&mut options.settings.victory_mode, VictoryMode::LastDistrict, "Last District");
+                    });
+              });
+
+              ui.horizontal(|ui| {
+                  ui.label("Kill Credit ");
+                  egui::ComboBox::from_id_source("kill_credit")
+                    .selected_text(format!("{:?}", options.settings.kill_credit))
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut options.settings.kill_credit, KillCredit::Everyone, "Every Killer");
+                        ui.selectable_value(&mut options.settings.kill_credit, KillCredit::FirstKiller, "First Killer, Others Assist");
+                    });
+              });
+
+              ui.checkbox(&mut options.settings.show_death_causes, "List how the fallen tributes died");
+
+              ui.checkbox(&mut options.settings.lenient, "Skip files that can't be loaded");
+
+              ui.separator();
+
+              ui.horizontal(|ui| {
+                  if ui.button("Save").clicked() && self.save_options(&mut options) {
+                      options.status = Some(format!("Saved to {}", self.settings_path));
+                  }
+
+                  if ui.button("Save & Rebuild").clicked() && self.save_options(&mut options) {
+                      // any errors end up in the error window
+                      self.rebuild_sim();
+                      self.app_state = AppState::MainMenu;
+                      close = true;
+                  }
+
+                  if ui.button("Cancel").clicked() {
+                      close = true;
+                  }
+              });
+
+              if let Some(status) = &options.status {
+                  ui.label(status.clone());
+              }
+          });
+
+        if open && !close {
+            self.options = Some(options);
+        }
+    }
+
+    // Applies the edited options and writes them to disk, returns
+    // whether or not that succeeded
+    fn save_options(&mut self, options: &mut OptionsState) -> bool {
+        options.settings.seed = if options.random_seed {
+            None
+        } else {
+            match options.seed.trim().parse::<u64>() {
+                Ok(seed) if seed <= MAX_SEED => Some(seed),
+                _ => {
+                    options.status = Some(format!("\"{}\" is not a valid seed, it should be a number up to {}", options.seed, MAX_SEED));
+                    return false
+                }
+            }
+        };
+
+        match options.settings.save(&self.settings_path) {
+            Ok(settings) => {
+                self.settings = settings;
+                true
+            },
+            Err(err) => {
+                options.status = Some(format!("{err}"));
+                false
+            }
+        }
+    }
+}
+
+fn folder_list(ui: &mut Ui, folders: &mut Vec<String>) {
+    let mut removed = None;
+
+    for (i, folder) in folders.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.text_edit_singleline(folder);
+
+            if ui.button("Remove").clicked() {
+                removed = Some(i);
+            }
+        });
+    }
+
+    if let Some(i) = removed {
+        folders.remove(i);
+    }
+
+    if ui.button("Add Folder").clicked() {
+        folders.push(String::new());
+    }
+}
+
+// A checkbox for every pack. Changes only apply after a rebuild
+fn pack_list(ui: &mut Ui, packs: &[EventPack], disabled_packs: &mut Vec<String>) {
+    for pack in packs {
+        let name = pack.get_name();
+        let mut enabled = !disabled_packs.iter().any(|disabled| disabled == name);
+
+        let mut hover_text = format!("{} events", pack.events.len());
+        if !pack.description.is_empty() {
+            hover_text = format!("{}\n\n{}", pack.description, hover_text);
+        }
+
+        if ui.checkbox(&mut enabled, pack.get_title()).on_hover_text(hover_text).changed() {
+            if enabled {
+                disabled_packs.retain(|disabled| disabled != name);
+            } else {
+                disabled_packs.push(name.to_string());
+            }
+        }
+
+        if !pack.content_warnings.is_empty() {
+            ui.label(RichText::new(format!("Content warnings: {}", pack.content_warnings.join(", "))).small());
+        }
+    }
+}
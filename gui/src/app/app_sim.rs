@@ -1,62 +1,286 @@
-use eframe::{egui::{self, Ui, Context}};
-use hg::{event::EventResult, simulation::SimulationError};
+use eframe::{egui::{self, Ui, Context, RichText, Color32}};
+use hg::{simulation::SimulationError, summary::GameSummary, export::save_log, event::{EventResult, TextSpan}};
 
 use super::{HGSimApp, AppState};
 
 impl HGSimApp {
-    pub(super) fn simulation(&mut self, _ctx: &Context, ui: &mut Ui, title: String, sim_events: Vec<EventResult>, advance_step: bool) -> Result<(), SimulationError> {
-        let mut events = sim_events.clone();
+    pub(super) fn simulation(&mut self, _ctx: &Context, ui: &mut Ui, page: usize, advance_step: bool) -> Result<(), SimulationError> {
+        let mut page = page;
 
-        ui.vertical_centered_justified(|ui| {
-            ui.heading(title.clone())
-        });
+        let simulation = match &mut self.simulation {
+            Some(sim) => sim,
+            None => {
+                self.app_state = AppState::MainMenu;
+                return Ok(())
+            }
+        };
 
-        let is_end = self.simulation.is_end();
+        if (advance_step || simulation.get_history().is_empty()) && !simulation.is_end() {
+            simulation.step()?;
 
-        if advance_step && !is_end {
-            if let Err(err) = self.simulation.step() {
-                return Err(err)
-            }
+            // everything we need is in the history
+            simulation.get_clear_next_events();
 
-            events = self.simulation.get_clear_next_events();
+            page = simulation.get_history().len() - 1;
 
             self.app_state = AppState::Simulation {
-                title: if self.simulation.is_end() {
-                    self.simulation.get_category_title()
-                } else {
-                    title
-                },
-                events: events.clone(),
+                page,
                 advance_step: false
             };
         }
 
-        egui::ScrollArea::vertical().show(ui, |ui| {
+        let history = simulation.get_history();
+        let last_page = history.len() - 1;
+        let is_summary = page > last_page;
+
+        let title = match history.get(page) {
+            Some(phase) => phase.title.clone(),
+            None => simulation.get_category_title()
+        };
+
+        ui.vertical_centered_justified(|ui| {
+            ui.heading(title)
+        });
+
+        ui.horizontal(|ui| {
+            if ui.add_enabled(page > 0, egui::Button::new("Back")).clicked() {
+                page -= 1;
+            }
+
+            if ui.add_enabled(page < last_page, egui::Button::new("Forward")).clicked() {
+                page += 1;
+            }
+
+            let current_day = history.get(page).map(|phase| phase.day).unwrap_or(simulation.get_day());
+
+            egui::ComboBox::from_id_source("jump_to_day")
+              .selected_text(format!("Day {}", current_day))
+              .show_ui(ui, |ui| {
+                  let mut day = 0;
+
+                  for (i, phase) in history.iter().enumerate() {
+                      // jump to the first phase of each day
+                      if phase.day != day {
+                          day = phase.day;
+
+                          if ui.selectable_label(current_day == day, format!("Day {}", day)).clicked() {
+                              page = i;
+                          }
+                      }
+                  }
+              });
+
+            let board_text = if self.show_status_board { "Hide Status Board" } else { "Show Status Board" };
+            if ui.button(board_text).clicked() {
+                self.show_status_board = !self.show_status_board;
+            }
+        });
+
+        ui.separator();
+
+        let mut proceed = false;
+
+        let result = egui::ScrollArea::vertical().show(ui, |ui| {
             ui.vertical_centered(|ui| {
-                if is_end {
-                    if let Some(winner) = self.simulation.get_winner() {
-                        ui.label(format!("The winner is {} from {}!", winner.name, self.simulation.get_trib_dist_name(winner)));
+                if is_summary {
+                    if let Some(victory) = simulation.get_victory_text() {
+                        ui.heading(victory);
                     } else {
                         return Err(SimulationError::MissingTributesError { event: "win_event".to_string() })
                     }
+
+                    ui.separator();
+                    summary(ui, &simulation.get_summary());
+                    ui.separator();
+
+                    ui.horizontal(|ui| {
+                        ui.text_edit_singleline(&mut self.log_path);
+
+                        if ui.button("Save Log").clicked() {
+                            self.log_status = Some(match save_log(simulation, &self.log_path) {
+                                Ok(()) => format!("Saved game log to {}", self.log_path),
+                                Err(err) => format!("{err}")
+                            });
+                        }
+                    });
+
+                    if let Some(status) = &self.log_status {
+                        ui.label(status.clone());
+                    }
                 } else {
-                    for event in events {
-                        ui.label(event.text.clone());
+                    for event in &history[page].events {
+                        if let Some(id) = event_text(ui, event) {
+                            self.selected_tribute = Some(id);
+                        }
                     }
-                }
-    
-                if ui.button("Proceed").clicked() {
-                    self.app_state = AppState::Simulation {
-                        title: self.simulation.get_category_title(),
-                        events: Vec::new(),
-                        advance_step: true
+
+                    // the newest phase is the only one we can move on from
+                    if page == last_page && ui.button("Proceed").clicked() {
+                        proceed = true;
                     }
                 }
 
-                return Ok(())
-            })
+                Ok(())
+            }).inner
+        }).inner;
+
+        if proceed {
+            if simulation.is_end() {
+                // show the summary
+                page += 1;
+            } else {
+                self.app_state = AppState::Simulation {
+                    page,
+                    advance_step: true
+                };
+
+                return result
+            }
+        }
+
+        self.app_state = AppState::Simulation {
+            page,
+            advance_step: false
+        };
+
+        result
+    }
+}
+
+fn summary(ui: &mut Ui, summary: &GameSummary) {
+    ui.label(summary.get_days_text());
+
+    ui.collapsing("Final Placements", |ui| {
+        egui::Grid::new("summary_placements").striped(true).show(ui, |ui| {
+            for placement in &summary.placements {
+                ui.label(format!("{}.", placement.place));
+                ui.label(RichText::new(&placement.name).strong());
+                ui.label(&placement.district);
+                ui.label(placement.get_kills_text());
+                ui.end_row();
+            }
+        });
+    });
+
+    ui.collapsing("Kill Leaderboard", |ui| {
+        if summary.kill_leaderboard.is_empty() {
+            ui.label("Nobody killed anyone!");
+        }
+
+        egui::Grid::new("summary_kills").striped(true).show(ui, |ui| {
+            for placement in &summary.kill_leaderboard {
+                ui.label(RichText::new(&placement.name).strong());
+                ui.label(&placement.district);
+                ui.label(placement.get_kills_text());
+                ui.end_row();
+            }
         });
+    });
+
+    ui.collapsing("Districts", |ui| {
+        egui::Grid::new("summary_districts").striped(true).show(ui, |ui| {
+            ui.label(RichText::new("District").strong());
+            ui.label(RichText::new("Best Placement").strong());
+            ui.label(RichText::new("Kills").strong());
+            ui.label(RichText::new("Survivors").strong());
+            ui.end_row();
+
+            for dist in &summary.districts {
+                ui.label(&dist.name);
+                ui.label(dist.best_place.to_string());
+                ui.label(dist.kills.to_string());
+                ui.label(dist.survivors.to_string());
+                ui.end_row();
+            }
+        });
+    });
+
+    ui.collapsing("Who Killed Whom", |ui| {
+        for kill in &summary.kills {
+            ui.label(kill.get_text());
+        }
+    });
+}
+
+// Shows an event with clickable tribute names,
+// returning the ID of whoever was clicked on
+pub(super) fn event_text(ui: &mut Ui, event: &EventResult) -> Option<String> {
+    let mut clicked = None;
+
+    ui.horizontal_wrapped(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+
+        for span in &event.spans {
+            match span {
+                TextSpan::Tribute { id, name } => {
+                    let name = if event.killed.contains(id) {
+                        RichText::new(name).strong().color(Color32::RED)
+                    } else {
+                        RichText::new(name).strong()
+                    };
 
-        return Ok(())
+                    let link = ui.add(egui::Label::new(name.underline()).sense(egui::Sense::click()));
+
+                    if link.on_hover_text("Show tribute").clicked() {
+                        clicked = Some(id.clone());
+                    }
+                },
+                TextSpan::Item(item) => {
+                    ui.label(RichText::new(item).italics());
+                },
+                span => {
+                    ui.label(span.get_text().trim_end_matches('\n'));
+                }
+            }
+        }
+    });
+
+    clicked
+}
+
+// Status board
+impl HGSimApp {
+    pub(super) fn status_board(&mut self, ctx: &Context) {
+        let simulation = match &self.simulation {
+            Some(sim) => sim,
+            None => return
+        };
+
+        egui::SidePanel::right("status_board").show(ctx, |ui| {
+            ui.vertical_centered(|ui| {
+                ui.heading("Status Board");
+            });
+
+            ui.separator();
+
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                egui::Grid::new("status_board_grid")
+                  .striped(true)
+                  .show(ui, |ui| {
+                      for dist in simulation.get_status_board() {
+                          ui.label(RichText::new(&dist.name).strong());
+
+                          for trib in &dist.tributes {
+                              ui.vertical(|ui| {
+                                  if trib.is_alive {
+                                      ui.label(RichText::new(&trib.name).strong());
+                                  } else {
+                                      ui.label(RichText::new(&trib.name).strong().strikethrough().color(Color32::RED));
+                                  }
+
+                                  ui.label(RichText::new(trib.get_state_text()).small());
+                                  ui.label(RichText::new(trib.get_kills_text()).small());
+
+                                  if let Some(killers) = trib.get_killers_text() {
+                                      ui.label(RichText::new(killers).small());
+                                  }
+                              });
+                          }
+
+                          ui.end_row();
+                      }
+                  });
+            });
+        });
     }
-}
\ No newline at end of file
+}
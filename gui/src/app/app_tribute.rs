@@ -0,0 +1,86 @@
+use eframe::egui::{self, Context, RichText, Color32};
+
+use super::{HGSimApp, app_sim::event_text};
+
+// Tribute window
+impl HGSimApp {
+    pub(super) fn tribute_window(&mut self, ctx: &Context) {
+        let id = match &self.selected_tribute {
+            Some(id) => id.clone(),
+            None => return
+        };
+
+        let simulation = match &self.simulation {
+            Some(sim) => sim,
+            None => return
+        };
+
+        let status = simulation.get_status_board()
+            .into_iter()
+            .flat_map(|dist| dist.tributes.into_iter().map(move |trib| (dist.name.clone(), trib)))
+            .find(|(_, trib)| trib.id == id);
+
+        // the tribute isn't in this game anymore, e.g. their district was removed
+        let (district, trib) = match status {
+            Some(status) => status,
+            None => {
+                self.selected_tribute = None;
+                return
+            }
+        };
+
+        let mut open = true;
+        let mut clicked = None;
+
+        egui::Window::new(trib.name.clone())
+          .id(egui::Id::new("tribute_window"))
+          .open(&mut open)
+          .collapsible(false)
+          .show(ctx, |ui| {
+              ui.label(RichText::new(&district).strong());
+
+              if trib.is_alive {
+                  ui.label(trib.get_state_text());
+              } else {
+                  ui.label(RichText::new(trib.get_state_text()).color(Color32::RED));
+              }
+
+              ui.label(trib.get_kills_text());
+
+              if let Some(killers) = trib.get_killers_text() {
+                  ui.label(killers);
+              }
+
+              if let Some(cause) = &trib.death_cause {
+                  ui.label(RichText::new(cause).italics());
+              }
+
+              ui.separator();
+
+              egui::ScrollArea::vertical().show(ui, |ui| {
+                  for entry in simulation.get_timeline(&id) {
+                      let event = match simulation.get_history().get(entry.phase).and_then(|phase| phase.events.get(entry.event)) {
+                          Some(event) => event,
+                          None => continue
+                      };
+
+                      ui.label(RichText::new(&entry.title).small());
+
+                      if let Some(id) = event_text(ui, event) {
+                          clicked = Some(id);
+                      }
+
+                      for fact in &entry.facts {
+                          ui.label(RichText::new(format!("• {}", fact.get_text(simulation))).small().weak());
+                      }
+                  }
+              });
+          });
+
+        if !open {
+            self.selected_tribute = None;
+        } else if clicked.is_some() {
+            self.selected_tribute = clicked;
+        }
+    }
+}
@@ -1,16 +1,21 @@
 use eframe::{egui, epi};
-use hg::{simulation::{Simulation, SimulationError}, event::{EventResult, Event}};
+use hg::{simulation::{Simulation, SimulationError, build_sim}, event::Event, simulation_settings::SimulationSettings};
 
 pub mod app_sim;
 pub mod app_main_menu;
 pub mod app_event_editor;
+pub mod app_options;
+pub mod app_error;
+pub mod app_tribute;
+pub mod app_import;
 
 #[derive(Clone)]
 pub enum AppState {
     MainMenu,
     Simulation {
-        title: String,
-        events: Vec<EventResult>,
+        // index into the simulation's history. One past the
+        // last phase is the summary, once the game is over
+        page: usize,
         advance_step: bool
     },
     EventEditor(EventEditorState)
@@ -40,20 +45,83 @@ pub struct EditedEvent {
     killed: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct OptionsState {
+    pub settings: SimulationSettings,
+    pub random_seed: bool,
+    pub seed: String,
+    pub status: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportState {
+    pub csv_path: String,
+    pub dir: String,
+    // where the last import was saved to, so it can be used right away
+    pub imported_dir: Option<String>,
+    pub status: Option<String>,
+    pub problems: Vec<String>,
+}
+
 pub struct HGSimApp {
     pub(super) app_state: AppState,
 
-    // simulation state
-    pub(super) simulation: Simulation,
+    // simulation state. This is `None` when the event
+    // or district files could not be loaded
+    pub(super) simulation: Option<Simulation>,
+    pub(super) settings: SimulationSettings,
+    // where the options window saves the settings to
+    pub(super) settings_path: String,
     pub(super) error: Option<SimulationError>,
+
+    // the options window is open whenever this is `Some`
+    pub(super) options: Option<OptionsState>,
+    // same for the import window
+    pub(super) import: Option<ImportState>,
+    pub(super) show_status_board: bool,
+    // the tribute window shows whoever was last clicked on
+    pub(super) selected_tribute: Option<String>,
+
+    // where "Save Log" writes the game log to, and how that went
+    pub(super) log_path: String,
+    pub(super) log_status: Option<String>,
 }
 
 impl HGSimApp {
-    pub fn new(simulation: Simulation) -> Self {
-        Self {
+    pub fn new(settings: SimulationSettings, settings_path: &str) -> Self {
+        let mut app = Self {
             app_state: AppState::MainMenu,
-            simulation,
+            simulation: None,
+            settings,
+            settings_path: String::from(settings_path),
             error: None,
+            options: None,
+            import: None,
+            show_status_board: false,
+            selected_tribute: None,
+            log_path: String::from("game_log.txt"),
+            log_status: None,
+        };
+
+        app.rebuild_sim();
+
+        app
+    }
+
+    // (Re)loads all of the event and district files, starting
+    // a brand new game. Returns whether or not that succeeded
+    pub(super) fn rebuild_sim(&mut self) -> bool {
+        match build_sim(self.settings.clone()) {
+            Ok(sim) => {
+                self.simulation = Some(sim);
+                self.error = None;
+                true
+            },
+            Err(err) => {
+                self.simulation = None;
+                self.error = Some(err);
+                false
+            }
         }
     }
 
@@ -72,19 +140,7 @@ impl epi::App for HGSimApp {
     fn update(&mut self, ctx: &egui::Context, frame: &epi::Frame) {
         //let Self { app_state, simulation, error} = self;
 
-        if let Some(err) = &self.error {
-            egui::Window::new("Simulation Error")
-            .collapsible(false)
-            .resizable(false)
-            .show(ctx, |ui| {
-                ui.label(format!("{err}"));
-                if ui.button("OK").clicked() {
-                    frame.quit();
-                    eprintln!("An error occurred while running simulation: {err}");
-                    std::process::exit(1);
-                }
-            });
-        }
+        self.error_window(ctx);
 
         egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
             // The top panel is often a good place for a menu bar:
@@ -100,7 +156,13 @@ impl epi::App for HGSimApp {
         egui::SidePanel::left("side_panel").resizable(false).show(ctx, |ui| {
             ui.label("");
             ui.vertical_centered(|ui| {
-                ui.button("Options")
+                if ui.button("Options").clicked() {
+                    self.toggle_options();
+                }
+
+                if ui.button("Import Cast").clicked() {
+                    self.toggle_import();
+                }
             });
 
             ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
@@ -116,7 +178,20 @@ impl epi::App for HGSimApp {
             });
         });
 
+        self.options_window(ctx);
+        self.import_window(ctx);
+
+        if matches!(self.app_state, AppState::Simulation { .. }) && self.show_status_board {
+            self.status_board(ctx);
+        }
+
+        self.tribute_window(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
+            // nothing should happen behind the error window
+            // until the user decides what to do about it
+            ui.set_enabled(self.error.is_none());
+
             ui.vertical_centered_justified(|ui| {
                 ui.heading("Hunger Games Simulator")
             });
@@ -128,11 +203,16 @@ impl epi::App for HGSimApp {
             match app_state {
                 AppState::MainMenu =>  self.main_menu(ctx, ui),
                 AppState::Simulation {
-                    title,
-                    events,
+                    page,
                     advance_step
                 } => {
-                    if let Err(err) = self.simulation(ctx, ui, title, events, advance_step) {
+                    if let Err(err) = self.simulation(ctx, ui, page, advance_step && self.error.is_none()) {
+                        // hold off on stepping again, the error window
+                        // decides whether or not we retry
+                        self.app_state = AppState::Simulation {
+                            page,
+                            advance_step: false
+                        };
                         self.error = Some(err);
                     }
                 },
@@ -1,9 +1,9 @@
-use hg::simulation::Simulation;
+use hg::simulation_settings::SimulationSettings;
 
 pub mod app;
 
-pub fn run(simulation: Simulation) {
-    let app = app::HGSimApp::new(simulation);
+pub fn run(settings: SimulationSettings, settings_path: &str) {
+    let app = app::HGSimApp::new(settings, settings_path);
     let native_options = eframe::NativeOptions::default();
     //native_options.initial_window_size = Some(Vec2 { x: 1920.0, y: 720.0 });
 
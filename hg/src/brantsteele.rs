@@ -0,0 +1,380 @@
+use regex::Regex;
+
+use crate::{import::{ImportProblem, CastImport}, event::{Event, EventCategory}, event_pack::EventPack, district::District, tribute::Tribute, pronouns::Pronouns, data_trait::{DataTrait, DataFormat}};
+
+// Converts the event lists and casts from BrantSteele's simulator
+// (and the ones people copy out of it) into our events and districts.
+//
+// Event lists are one event per line, under a section header:
+//
+// Bloodbath
+// (Player1) grabs a shovel.
+// Fatal Day
+// (Player1) pushes (Player2) off a cliff. [killed: 2] [killers: 1]
+//
+// Pronouns become (they1) and friends, but the verbs around them
+// are left alone, so "(They1) runs" may need touching up afterwards.
+//
+// Casts are one tribute per line, "name | gender | image", under a
+// district header. Tributes without one are paired up, two per district:
+//
+// District 1
+// Katniss Everdeen | female | https://example.com/katniss.png
+
+pub struct EventImport {
+    pub pack: EventPack,
+    pub problems: Vec<ImportProblem>
+}
+
+enum Section {
+    // the category, and whether or not everything in it is fatal
+    Supported(EventCategory, bool),
+    // sections we have no equivalent for, like the Feast
+    Unsupported(String)
+}
+
+pub fn import_events(contents: &str, pack_name: &str) -> EventImport {
+    let annotation_regex = Regex::new(r"(?i)\[\s*(killed|killers)\s*:([^\]]*)\]").unwrap();
+
+    let mut events = Vec::new();
+    let mut problems = Vec::new();
+    let mut section = None;
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let problem = |reason: &str| ImportProblem {
+            line: i + 1,
+            text: line.to_string(),
+            reason: reason.to_string()
+        };
+
+        if let Some(new_section) = get_section(line) {
+            if let Section::Unsupported(name) = &new_section {
+                problems.push(problem(&format!("{} events aren't supported, skipping the whole section", name)));
+            }
+
+            section = Some(new_section);
+            continue;
+        }
+
+        let (category, fatal) = match &section {
+            Some(Section::Supported(category, fatal)) => (category.clone(), *fatal),
+            Some(Section::Unsupported(_)) => continue,
+            None => {
+                problems.push(problem("not under a Bloodbath, Day or Night header"));
+                continue;
+            }
+        };
+
+        // [killed: 2] and [killers: 1] say who dies and who did it
+        let mut killed = Vec::new();
+        let mut killers = Vec::new();
+        let mut bad_annotation = false;
+
+        for cap in annotation_regex.captures_iter(line) {
+            for num in cap[2].split(',').map(|num| num.trim()).filter(|num| !num.is_empty()) {
+                match num.parse::<u32>() {
+                    Ok(num) if cap[1].to_lowercase() == "killed" => killed.push(num),
+                    Ok(num) => killers.push(num),
+                    Err(_) => bad_annotation = true
+                }
+            }
+        }
+
+        if bad_annotation {
+            problems.push(problem("[killed: ...] and [killers: ...] should only list player numbers"));
+            continue;
+        }
+
+        if fatal && killed.is_empty() {
+            problems.push(problem("fatal events need [killed: ...] to say who dies"));
+            continue;
+        }
+
+        // deaths don't need a killer, but a killer needs a death
+        if killed.is_empty() && !killers.is_empty() {
+            problems.push(problem("[killers: ...] needs a [killed: ...] to go with it"));
+            continue;
+        }
+
+        let text = annotation_regex.replace_all(line, "").trim().to_string();
+
+        let (text, players) = match convert_text(&text) {
+            Ok(converted) => converted,
+            Err(reason) => {
+                problems.push(problem(&reason));
+                continue;
+            }
+        };
+
+        if let Some(num) = killed.iter().chain(killers.iter()).find(|num| !players.contains(num)) {
+            problems.push(problem(&format!("Player{} is listed as killed or a killer, but isn't in the event", num)));
+            continue;
+        }
+
+        events.push(Event {
+            file_name: String::new(),
+            format: DataFormat::Toml,
+            text,
+            killed: killed.iter().map(|num| format!("(P{})", num)).collect(),
+            killers: killers.iter().map(|num| format!("(P{})", num)).collect(),
+            assists: Vec::new(),
+            injured: Vec::new(),
+            allies: Vec::new(),
+            cause: String::new(),
+            category,
+            weight: 50,
+            script: String::new(),
+            compiled_script: None,
+            follow_ups: Vec::new(),
+            outcomes: Vec::new()
+        });
+    }
+
+    let mut pack = EventPack {
+        file_name: String::new(),
+        format: DataFormat::Toml,
+        is_single: false,
+        name: String::from(pack_name),
+        author: String::new(),
+        version: String::new(),
+        description: String::from("Imported from BrantSteele's simulator"),
+        content_warnings: Vec::new(),
+        events
+    };
+    pack.set_path(&format!("{}.toml", pack_name));
+
+    EventImport { pack, problems }
+}
+
+// e.g. "Bloodbath", "Fatal Day:" or "[Night]"
+fn get_section(line: &str) -> Option<Section> {
+    let title = line.trim_matches(['[', ']', ':']).trim();
+    let name = title.to_lowercase();
+
+    let fatal = name.starts_with("fatal ");
+    let name = name.strip_prefix("fatal ").unwrap_or(&name);
+    let name = name.strip_suffix(" events").unwrap_or(name);
+
+    match name {
+        "bloodbath" => Some(Section::Supported(EventCategory::Bloodbath, fatal)),
+        "day" => Some(Section::Supported(EventCategory::Day, fatal)),
+        "night" => Some(Section::Supported(EventCategory::Night, fatal)),
+        "feast" | "arena" => Some(Section::Unsupported(title.to_string())),
+        _ => None
+    }
+}
+
+// Swaps (Player1) for (P1) and (he/she1) for (they1) and so on. Returns
+// the converted text and the player numbers in it, or why it couldn't
+pub fn convert_text(text: &str) -> Result<(String, Vec<u32>), String> {
+    let token_regex = Regex::new(r"\(([^()]*)\)").unwrap();
+    let numbered_regex = Regex::new(r"^([A-Za-z/]+)\s*([0-9]+)$").unwrap();
+
+    let mut players = Vec::new();
+    let mut pronoun_players = Vec::new();
+    let mut error = None;
+
+    let converted = token_regex.replace_all(text, |cap: &regex::Captures| {
+        let token = cap[1].trim();
+
+        let numbered = match numbered_regex.captures(token) {
+            Some(numbered) => numbered,
+            // plain old parentheses
+            None => return cap[0].to_string()
+        };
+
+        let word = &numbered[1];
+        let num = numbered[2].parse::<u32>().unwrap_or(0);
+
+        if !(1..=9).contains(&num) {
+            error.get_or_insert(format!("`{}` is out of range, only Player1 to Player9 are supported", &cap[0]));
+            return cap[0].to_string();
+        }
+
+        let is_upper = word.chars().next().map(|c| c.is_uppercase()).unwrap_or(false);
+
+        let pronoun = match word.to_lowercase().as_str() {
+            "player" => {
+                if !players.contains(&num) {
+                    players.push(num);
+                }
+
+                return format!("(P{})", num)
+            },
+            "he/she" | "she/he" | "he" | "she" => "they",
+            "him/her" | "her/him" | "him" => "them",
+            "his/her" | "her/his" | "his" => "their",
+            "himself/herself" | "herself/himself" | "himself" | "herself" => "themself",
+            _ => {
+                error.get_or_insert(format!("don't know what to do with `{}`", &cap[0]));
+                return cap[0].to_string();
+            }
+        };
+
+        pronoun_players.push(num);
+
+        if is_upper {
+            let mut chars = pronoun.chars();
+            let first = chars.next().unwrap_or_default().to_uppercase();
+
+            format!("({}{}{})", first, chars.as_str(), num)
+        } else {
+            format!("({}{})", pronoun, num)
+        }
+    }).to_string();
+
+    if let Some(error) = error {
+        return Err(error);
+    }
+
+    if players.is_empty() {
+        return Err(String::from("there are no players in the event"));
+    }
+
+    if let Some(num) = pronoun_players.iter().find(|num| !players.contains(num)) {
+        return Err(format!("uses pronouns for Player{}, who isn't in the event", num));
+    }
+
+    players.sort_unstable();
+
+    Ok((converted, players))
+}
+
+pub fn import_cast(contents: &str) -> CastImport {
+    let header_regex = Regex::new(r"(?i)^district\s*([0-9]+)\s*:?$").unwrap();
+
+    let mut districts: Vec<District> = Vec::new();
+    let mut problems = Vec::new();
+    // whether or not the last district was made up for tributes without a header
+    let mut paired = false;
+
+    for (i, line) in contents.lines().enumerate() {
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if let Some(cap) = header_regex.captures(line) {
+            districts.push(District::new(&format!("District {}", &cap[1]), Vec::new()));
+            paired = false;
+            continue;
+        }
+
+        let fields = line
+            .split(['|', '\t'])
+            .map(|field| field.trim())
+            .collect::<Vec<&str>>();
+
+        let name = fields[0];
+        if name.is_empty() {
+            problems.push(ImportProblem {
+                line: i + 1,
+                text: line.to_string(),
+                reason: String::from("the tribute has no name")
+            });
+            continue;
+        }
+
+        let pronouns = match fields.get(1).map(|gender| get_pronouns(gender)) {
+            Some(Some(pronouns)) => pronouns,
+            _ => {
+                problems.push(ImportProblem {
+                    line: i + 1,
+                    text: line.to_string(),
+                    reason: String::from("unknown or missing gender, using They")
+                });
+                Pronouns::They
+            }
+        };
+
+        let tribute = Tribute::new(name, fields.get(2).unwrap_or(&""), pronouns);
+
+        let needs_district = match districts.last() {
+            None => true,
+            Some(dist) => paired && dist.tributes.len() >= 2
+        };
+
+        if needs_district {
+            districts.push(District::new(&format!("District {}", districts.len() + 1), Vec::new()));
+            paired = true;
+        }
+
+        if let Some(dist) = districts.last_mut() {
+            dist.tributes.push(tribute);
+        }
+    }
+
+    // headers without anyone under them
+    districts.retain(|dist| !dist.tributes.is_empty());
+
+    for (i, dist) in districts.iter_mut().enumerate() {
+        dist.set_path(&format!("district_{}.toml", i + 1));
+    }
+
+    CastImport { districts, problems }
+}
+
+fn get_pronouns(gender: &str) -> Option<Pronouns> {
+    match gender.trim().to_lowercase().as_str() {
+        "m" | "male" | "boy" | "man" => Some(Pronouns::He),
+        "f" | "female" | "girl" | "woman" => Some(Pronouns::She),
+        "n" | "nb" | "nonbinary" | "non-binary" | "other" => Some(Pronouns::They),
+        other => other.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_players_and_pronouns() {
+        let (text, players) = convert_text("(Player2) pushes (Player1) off a cliff with (his/her2) shovel. (He/She1) falls.").unwrap();
+
+        assert_eq!(text, "(P2) pushes (P1) off a cliff with (their2) shovel. (They1) falls.");
+        assert_eq!(players, vec![ 1, 2 ]);
+    }
+
+    #[test]
+    fn rejects_unknown_tokens_and_stray_pronouns() {
+        assert!(convert_text("(Player10) waves.").is_err());
+        assert!(convert_text("(Player1) waves at (him/her2).").is_err());
+        assert!(convert_text("Nobody is here (yet).").is_err());
+        // parentheses that aren't tokens are left alone
+        assert_eq!(convert_text("(Player1) waves (happily).").unwrap().0, "(P1) waves (happily).");
+    }
+
+    #[test]
+    fn imports_cast_under_headers() {
+        let cast = import_cast("District 3\nKatniss | female\nPeeta | m | peeta.png\nRue | f\n\nDistrict 5:\nFoxface | ?");
+
+        assert_eq!(cast.districts.len(), 2);
+        assert_eq!(cast.districts[0].name, "District 3");
+        assert_eq!(cast.districts[0].tributes.len(), 3);
+        assert_eq!(cast.districts[0].tributes[1].avatar_url, "peeta.png");
+        assert!(cast.districts[0].tributes[1].pronouns == Pronouns::He);
+
+        // the unknown gender is reported, and falls back to They
+        assert_eq!(cast.problems.len(), 1);
+        assert_eq!(cast.problems[0].line, 7);
+        assert!(cast.districts[1].tributes[0].pronouns == Pronouns::They);
+    }
+
+    #[test]
+    fn pairs_up_tributes_without_headers() {
+        let cast = import_cast("Katniss | f\nPeeta | m\nRue | f\nThresh | m\nClove | f");
+
+        let sizes = cast.districts.iter().map(|dist| dist.tributes.len()).collect::<Vec<usize>>();
+
+        assert_eq!(sizes, vec![ 2, 2, 1 ]);
+        assert_eq!(cast.districts[2].name, "District 3");
+        assert!(cast.problems.is_empty());
+    }
+}
@@ -0,0 +1,197 @@
+use rand::{distributions::WeightedIndex, prelude::{SliceRandom, Distribution}, rngs::StdRng, SeedableRng};
+use serde::{Serialize, Deserialize};
+
+use crate::{district::District, tribute::Tribute, pronouns::Pronouns, simulation::SimulationError, data_trait::FileError};
+
+// Used when no names or name lists are given
+const DEFAULT_NAMES: &[&str] = &[
+    "Alex", "Avery", "Blake", "Cameron", "Casey", "Charlie", "Dakota", "Drew",
+    "Eden", "Elliot", "Emerson", "Finley", "Frankie", "Harper", "Hayden", "Jamie",
+    "Jesse", "Jordan", "Kai", "Kendall", "Logan", "Marley", "Morgan", "Parker",
+    "Peyton", "Quinn", "Reese", "Riley", "River", "Robin", "Rowan", "Sage",
+    "Sam", "Sawyer", "Skyler", "Spencer", "Sydney", "Taylor", "Toby", "Wren"
+];
+
+// How likely each set of pronouns is, relative to the others
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PronounWeights {
+    #[serde(default)]
+    pub he: u32,
+    #[serde(default)]
+    pub she: u32,
+    #[serde(default)]
+    pub they: u32,
+    #[serde(default)]
+    pub it: u32
+}
+
+impl Default for PronounWeights {
+    fn default() -> Self {
+        Self {
+            he: 45,
+            she: 45,
+            they: 10,
+            it: 0
+        }
+    }
+}
+
+// Makes up a cast instead of loading one from the district folders, e.g.
+//
+// [random_cast]
+// districts = 12
+// tributes_per_district = 2
+// name_lists = [ "names.txt" ]
+// pronouns = { he = 1, she = 1, they = 1 }
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CastSettings {
+    pub districts: u32,
+    pub tributes_per_district: u32,
+    // names to pick from, along with the ones in the name list files
+    // (one name per line). If there are none, a built-in list is used
+    #[serde(default)]
+    pub names: Vec<String>,
+    #[serde(default)]
+    pub name_lists: Vec<String>,
+    #[serde(default)]
+    pub pronouns: PronounWeights,
+    // if no seed is given, the game's seed is used
+    #[serde(default)]
+    pub seed: Option<u64>
+}
+
+impl CastSettings {
+    pub fn new(districts: u32, tributes_per_district: u32) -> Self {
+        Self {
+            districts,
+            tributes_per_district,
+            names: Vec::new(),
+            name_lists: Vec::new(),
+            pronouns: PronounWeights::default(),
+            seed: None
+        }
+    }
+}
+
+// Builds `districts` districts of `tributes_per_district` tributes each.
+// Names aren't reused until they run out, then they get numbered ("Sam 2")
+pub fn generate_cast(settings: &CastSettings, seed: u64) -> Result<Vec<District>, SimulationError> {
+    let mut rng = StdRng::seed_from_u64(settings.seed.unwrap_or(seed));
+
+    let mut names = settings.names.clone();
+    for file in &settings.name_lists {
+        names.append(&mut read_name_list(file)?);
+    }
+
+    if names.is_empty() {
+        names = DEFAULT_NAMES.iter().map(|name| name.to_string()).collect();
+    }
+
+    names.sort();
+    names.dedup();
+
+    let pronouns = [
+        (Pronouns::He, settings.pronouns.he),
+        (Pronouns::She, settings.pronouns.she),
+        (Pronouns::They, settings.pronouns.they),
+        (Pronouns::It, settings.pronouns.it)
+    ];
+
+    let pronoun_dist = WeightedIndex::new(pronouns.iter().map(|(_, weight)| *weight))
+        .map_err(|source| SimulationError::WeightedRandomError { source })?;
+
+    let mut pool = Vec::new();
+    let mut round = 1;
+    let mut districts = Vec::new();
+
+    for i in 0..settings.districts {
+        let mut tributes = Vec::new();
+
+        for _ in 0..settings.tributes_per_district {
+            if pool.is_empty() {
+                pool = names.iter().map(|name| match round {
+                    1 => name.clone(),
+                    _ => format!("{} {}", name, round)
+                }).collect();
+                pool.shuffle(&mut rng);
+                round += 1;
+            }
+
+            let name = pool.pop().unwrap_or_default();
+            let pronouns = pronouns[pronoun_dist.sample(&mut rng)].0.clone();
+
+            tributes.push(Tribute::new(&name, "", pronouns));
+        }
+
+        let mut dist = District::new(&format!("District {}", i + 1), tributes);
+        dist.file_name = format!("district_{}", i + 1);
+        dist.fill_tribute_ids();
+        districts.push(dist);
+    }
+
+    Ok(districts)
+}
+
+fn read_name_list(file: &str) -> Result<Vec<String>, SimulationError> {
+    let contents = std::fs::read_to_string(file).map_err(|source|
+        SimulationError::FileError {
+            file: file.to_string(),
+            source: FileError::FileReadError {
+                file: file.to_string(),
+                source
+            }
+        }
+    )?;
+
+    Ok(contents
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| line.to_string())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(districts: &[District]) -> Vec<String> {
+        districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter().map(|trib| trib.name.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn same_seed_same_cast() {
+        let settings = CastSettings::new(4, 3);
+
+        let first = generate_cast(&settings, 42).unwrap();
+        let second = generate_cast(&settings, 42).unwrap();
+
+        assert_eq!(first.len(), 4);
+        assert!(first.iter().all(|dist| dist.tributes.len() == 3));
+        assert_eq!(first[3].name, "District 4");
+        assert_eq!(names(&first), names(&second));
+    }
+
+    #[test]
+    fn numbers_names_once_they_run_out() {
+        let mut settings = CastSettings::new(3, 1);
+        settings.names = vec![ String::from("Sam"), String::from("Sam") ];
+        settings.pronouns = PronounWeights { he: 0, she: 0, they: 1, it: 0 };
+
+        let cast = generate_cast(&settings, 1).unwrap();
+
+        assert_eq!(names(&cast), vec![ "Sam", "Sam 2", "Sam 3" ]);
+        assert!(cast.iter().all(|dist| dist.tributes[0].pronouns == Pronouns::They));
+    }
+
+    #[test]
+    fn needs_some_pronouns() {
+        let mut settings = CastSettings::new(1, 1);
+        settings.pronouns = PronounWeights { he: 0, she: 0, they: 0, it: 0 };
+
+        assert!(generate_cast(&settings, 1).is_err());
+    }
+}
@@ -1,3 +1,7 @@
+use std::path::Path;
+
+use serde::{Serialize, de::DeserializeOwned};
+
 #[derive(thiserror::Error, Debug)]
 pub enum FileError {
     // Represents a failure to read a file directory
@@ -28,6 +32,62 @@ pub enum FileError {
         source: toml::ser::Error,
     },
 
+    // Represents a failure to parse a JSON file
+    #[error("Could not parse file `{file:?}`: {source:?}")]
+    JSONParseError {
+        file: String,
+        source: serde_json::Error,
+    },
+
+    // Represents a failure to parse a YAML file
+    #[error("Could not parse file `{file:?}`: {source:?}")]
+    YAMLParseError {
+        file: String,
+        source: serde_yaml::Error,
+    },
+
+    // Represents a failure to serialize a YAML file
+    #[error("Could not serialize file `{file:?}`: {source:?}")]
+    YAMLSerializeError {
+        file: String,
+        source: serde_yaml::Error,
+    },
+
+    // Represents a failure to parse a RON file
+    #[error("Could not parse file `{file:?}`: {source:?}")]
+    RONParseError {
+        file: String,
+        // boxed, it would make every FileError a lot bigger otherwise
+        source: Box<ron::error::SpannedError>,
+    },
+
+    // Represents a failure to serialize a RON file
+    #[error("Could not serialize file `{file:?}`: {source:?}")]
+    RONSerializeError {
+        file: String,
+        source: Box<ron::Error>,
+    },
+
+    // Represents a failure to compile an event's script
+    #[error("Could not compile script `{file:?}`: {source}")]
+    ScriptParseError {
+        file: String,
+        source: Box<rhai::ParseError>,
+    },
+
+    // Represents a file that isn't in any format we know of
+    #[error("Unknown file format for `{file:?}`, expected .toml, .json, .yaml, .yml or .ron")]
+    UnknownFormatError {
+        file: String,
+    },
+
+    // Represents a failure to serialize a JSON file
+    #[error("Could not serialize file `{file:?}`: {source:?}")]
+    JSONSerializeError {
+        file: String,
+        source: serde_json::Error,
+    },
+
     // Represents a failure to write a file
     #[error("Could not write file `{file:?}`: {source:?}")]
     FileWriteError {
@@ -40,10 +100,150 @@ pub enum FileError {
     IOError { source: std::io::Error }
 }
 
+impl FileError {
+    // Where in the file things went wrong, if the parser told us.
+    // Lines and columns start at 1, like in a text editor
+    pub fn get_position(&self) -> Option<(usize, usize)> {
+        match self {
+            FileError::TOMLParseError { source, .. } => source.line_col().map(|(line, col)| (line + 1, col + 1)),
+            // serde_json uses line 0 for errors that aren't about the syntax
+            FileError::JSONParseError { source, .. } if source.line() > 0 => Some((source.line(), source.column())),
+            FileError::YAMLParseError { source, .. } => source.location().map(|loc| (loc.line(), loc.column())),
+            FileError::RONParseError { source, .. } => Some((source.position.line, source.position.col)),
+            FileError::ScriptParseError { source, .. } => source.1.line().map(|line| (line, source.1.position().unwrap_or(1))),
+            _ => None
+        }
+    }
+
+    // The error itself, without the file name
+    pub fn get_message(&self) -> String {
+        match self {
+            // these put the position at the end of the message,
+            // we already have it from get_position()
+            FileError::TOMLParseError { source, .. } => strip_position(source.to_string()),
+            FileError::JSONParseError { source, .. } => strip_position(source.to_string()),
+            FileError::YAMLParseError { source, .. } => strip_position(source.to_string()),
+            FileError::RONParseError { source, .. } => source.code.to_string(),
+            FileError::ScriptParseError { source, .. } => source.0.to_string(),
+            FileError::DirectoryReadError { source, .. } |
+            FileError::FileReadError { source, .. } |
+            FileError::FileWriteError { source, .. } => source.to_string(),
+            err => err.to_string()
+        }
+    }
+}
+
+fn strip_position(message: String) -> String {
+    match message.rsplit_once(" at line ") {
+        Some((message, _)) => message.to_string(),
+        None => message
+    }
+}
+
+// The formats data files can be written in, picked by their extension
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum DataFormat {
+    #[default]
+    Toml,
+    Json,
+    Yaml,
+    Ron
+}
+
+impl DataFormat {
+    pub fn from_path(path: impl AsRef<Path>) -> Option<Self> {
+        let ext = path.as_ref().extension()?.to_str()?;
+
+        match ext.to_lowercase().as_str() {
+            "toml" => Some(DataFormat::Toml),
+            "json" => Some(DataFormat::Json),
+            "yaml" | "yml" => Some(DataFormat::Yaml),
+            "ron" => Some(DataFormat::Ron),
+            _ => None
+        }
+    }
+
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            DataFormat::Toml => "toml",
+            DataFormat::Json => "json",
+            DataFormat::Yaml => "yaml",
+            DataFormat::Ron => "ron"
+        }
+    }
+
+    pub fn parse<T: DeserializeOwned>(&self, file: &str, contents: &str) -> Result<T, FileError> {
+        let file = file.to_string();
+
+        match self {
+            DataFormat::Toml => toml::from_str(contents).map_err(|source| FileError::TOMLParseError { file, source }),
+            DataFormat::Json => serde_json::from_str(contents).map_err(|source| FileError::JSONParseError { file, source }),
+            DataFormat::Yaml => serde_yaml::from_str(contents).map_err(|source| FileError::YAMLParseError { file, source }),
+            DataFormat::Ron => ron::from_str(contents).map_err(|source| FileError::RONParseError { file, source: Box::new(source) })
+        }
+    }
+
+    pub fn serialize<T: Serialize>(&self, file: &str, data: &T) -> Result<String, FileError> {
+        let file = file.to_string();
+
+        match self {
+            DataFormat::Toml => toml::to_string(data).map_err(|source| FileError::TOMLSerializeError { file, source }),
+            DataFormat::Json => serde_json::to_string_pretty(data).map_err(|source| FileError::JSONSerializeError { file, source }),
+            DataFormat::Yaml => serde_yaml::to_string(data).map_err(|source| FileError::YAMLSerializeError { file, source }),
+            DataFormat::Ron => ron::ser::to_string_pretty(data, ron::ser::PrettyConfig::default())
+                .map_err(|source| FileError::RONSerializeError { file, source: Box::new(source) })
+        }
+    }
+}
+
+// Reads a data file in whichever format its extension says it's in
+pub fn read_data_file<T: DeserializeOwned>(file: &str) -> Result<T, FileError> {
+    let format = DataFormat::from_path(file).ok_or_else(||
+        FileError::UnknownFormatError {
+            file: file.to_string()
+        }
+    )?;
+
+    // Attempt to read the data in a file
+    let contents = std::fs::read_to_string(file).map_err(|source|
+        FileError::FileReadError {
+            file: file.to_string(),
+            source
+        }
+    )?;
+
+    format.parse(file, &contents)
+}
+
+pub fn write_data_file<T: Serialize>(file: &str, data: &T, format: DataFormat) -> Result<(), FileError> {
+    let contents = format.serialize(file, data)?;
+
+    std::fs::write(file, contents).map_err(|source|
+        FileError::FileWriteError {
+            file: file.to_string(),
+            source
+        }
+    )
+}
+
+// The name a data file goes by, which is its path inside the data
+// folder without the extension, e.g. "arena/district_1". Always uses
+// '/' so the same folder gives the same names (and IDs) on every OS
+pub fn get_data_name(path: &Path) -> String {
+    let name = path.with_extension("");
+
+    name
+        .components()
+        .map(|part| part.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
 pub trait DataTrait {
     type Output;
 
     fn from_file(file: &str) -> Result<Self::Output, FileError>;
 
+    // `file_name` is the file's path inside the data folder
     fn set_path(&mut self, file_name: &str);
 }
\ No newline at end of file
@@ -1,12 +1,17 @@
+use std::path::Path;
+
 use serde::{Serialize, Deserialize};
 
-use crate::{tribute::Tribute, data_trait::{DataTrait, FileError}};
+use crate::{tribute::Tribute, data_trait::{DataTrait, DataFormat, FileError, read_data_file, write_data_file, get_data_name}};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct District {
     #[serde(skip_serializing)]
     #[serde(default)]
     pub file_name: String,
+    // the format the file was loaded from, and will be saved in
+    #[serde(skip)]
+    pub format: DataFormat,
 
     pub name: String,
     pub tributes: Vec<Tribute>
@@ -16,31 +21,50 @@ impl DataTrait for District {
     type Output = District;
 
     fn from_file(file: &str) -> Result<District, FileError> {
-        // Attempt to read the data in a file
-        let contents = std::fs::read_to_string(file).map_err(|source|
-            FileError::FileReadError {
-                file: file.to_string(),
-                source
-            }
-        )?;
-
-        // Attempt to parse the data to TOML
-        let toml = toml::from_str(&contents).map_err(|source|
-            FileError::TOMLParseError {
-                file: file.to_string(),
-                source
-            }
-        )?;
-
-        Ok(toml)
+        read_data_file(file)
     }
 
     fn set_path(&mut self, file_name: &str) {
-        self.file_name = file_name.to_string().replace(".toml", "");
+        let path = Path::new(file_name);
+
+        self.file_name = get_data_name(path);
+        self.format = DataFormat::from_path(path).unwrap_or_default();
     }
 }
 
 impl District {
+    pub fn new(name: &str, tributes: Vec<Tribute>) -> Self {
+        Self {
+            file_name: String::new(),
+            format: DataFormat::Toml,
+            name: String::from(name),
+            tributes
+        }
+    }
+
+    // Writes the district to `dir`, in the format it was loaded from.
+    // Returns the path of the file
+    pub fn save(&self, dir: &str) -> Result<String, FileError> {
+        let path = Path::new(dir)
+            .join(format!("{}.{}", self.file_name, self.format.get_extension()))
+            .to_string_lossy()
+            .to_string();
+
+        write_data_file(&path, self, self.format)?;
+
+        Ok(path)
+    }
+
+    // Gives the tributes without an ID one made from
+    // the file and their place in it, e.g. "district_1:2"
+    pub fn fill_tribute_ids(&mut self) {
+        for (i, trib) in self.tributes.iter_mut().enumerate() {
+            if trib.get_id().is_empty() {
+                trib.set_id(&format!("{}:{}", self.file_name, i + 1));
+            }
+        }
+    }
+
     pub fn has_living_tributes(&self) -> bool {
         let mut living = 0;
 
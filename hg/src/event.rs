@@ -1,10 +1,12 @@
+use std::path::Path;
+
 use itertools::Itertools;
 use rand::Rng;
-use regex::Regex;
+use regex::{Regex, Captures};
 use serde::{Serialize, Deserialize};
 use hashbrown::HashMap;
 
-use crate::{tribute::Tribute, data_trait::{DataTrait, FileError}, pronouns::{they, themself, their, them, Pronouns}};
+use crate::{tribute::Tribute, script::{EventScript, ScriptContext}, data_trait::{DataTrait, DataFormat, FileError, read_data_file, write_data_file, get_data_name}, pronouns::{they, themself, their, them, Pronouns}};
 
 #[derive(thiserror::Error, Debug)]
 pub enum EventError {
@@ -25,6 +27,34 @@ pub enum EventError {
     #[error("The following event is missing at least 1 field: {event:?}")]
     MissingFieldsError {
         event: String,
+    },
+
+    #[error("Event \"{event:?}\" needs {required:?} tributes, but there are only {available:?}")]
+    NotEnoughTributes {
+        event: String,
+        required: usize,
+        available: usize,
+    },
+
+    #[error("The script for event \"{event:?}\" failed: {message}")]
+    ScriptError {
+        event: String,
+        message: String,
+    },
+
+    // Represents a follow-up using a player the event it follows doesn't have
+    #[error("Follow-up \"{event:?}\" uses (P{player_num:?}), but the event it follows only has {players:?}")]
+    UnknownFollowUpPlayer {
+        event: String,
+        player_num: u32,
+        players: Vec<u32>,
+    },
+
+    // Represents a follow-up in a phase that never picks up follow-ups
+    #[error("Follow-up \"{event:?}\" is a {category:?} event, but follow-ups can only be Bloodbath, Day or Night events")]
+    InvalidFollowUpCategory {
+        event: String,
+        category: EventCategory,
     }
 }
 
@@ -42,26 +72,139 @@ pub struct Event {
     #[serde(skip_serializing)]
     #[serde(default)]
     pub file_name: String,
+    // the format the file was loaded from, and will be saved in
+    #[serde(skip)]
+    pub format: DataFormat,
 
     pub text: String,
     #[serde(default)]
     pub killed: Vec<String>,
     #[serde(default)]
     pub killers: Vec<String>,
+    // players that helped with the kill, but don't get it
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assists: Vec<String>,
+    // players that get hurt, and players that team up, e.g. [ "(P1)", "(P2)" ].
+    // These only show up in the tributes' timelines
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub injured: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allies: Vec<String>,
+    // how whoever dies here died, e.g. "impaled by (P2)'s spear".
+    // If there isn't one, the event's text is used
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub cause: String,
     pub category: EventCategory,
     #[serde(default = "default_weight")]
     pub weight: i32,
+    // a Rhai script next to the event file, see script.rs
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub script: String,
+    #[serde(skip)]
+    pub compiled_script: Option<EventScript>,
+    // events that happen to the same cast in a later phase, e.g.
+    //
+    // text = "(P1) eats some poisonous berries."
+    // category = "Day"
+    //
+    // [[follow_ups]]
+    // text = "(P1) succumbs to the poison."
+    // category = "Night"
+    // killed = [ "(P1)" ]
+    //
+    // Each one's category is the phase it happens in, and its (P1), (P2)
+    // and so on are the same tributes as this event's. They're dropped
+    // if any of those tributes have died by then.
+    // Kept last along with the outcomes, since TOML
+    // tables have to come after the plain values
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub follow_ups: Vec<Event>,
+    // ways the event can play out, see EventOutcome
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outcomes: Vec<EventOutcome>,
+}
+
+// One way an event can end, picked by weight whenever the event comes up.
+// Its text goes after the event's, and the players it lists (killed,
+// killers and so on) are on top of the event's, so every ending shares
+// the same cast:
+//
+// text = "(P1) attacks (P2)."
+// category = "Day"
+//
+// [[outcomes]]
+// text = "(P1) wins the fight."
+// killed = [ "(P2)" ]
+// killers = [ "(P1)" ]
+//
+// [[outcomes]]
+// text = "They both run off."
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventOutcome {
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub killed: Vec<String>,
+    #[serde(default)]
+    pub killers: Vec<String>,
+    #[serde(default)]
+    pub assists: Vec<String>,
+    #[serde(default)]
+    pub injured: Vec<String>,
+    #[serde(default)]
+    pub allies: Vec<String>,
+    // replaces the event's cause, if it has one
+    #[serde(default)]
+    pub cause: String,
+    #[serde(default = "default_weight")]
+    pub weight: i32,
 }
 
 fn default_weight() -> i32 {
     50
 }
 
+// A piece of an event's text, so front-ends
+// can tell which parts refer to which tribute
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TextSpan {
+    Text(String),
+    Tribute {
+        id: String,
+        name: String
+    },
+    // pronouns, and words that depend on them, e.g. (P1)(is/are)
+    Pronoun {
+        tribute: String,
+        text: String
+    },
+    Item(String)
+}
+
+impl TextSpan {
+    pub fn get_text(&self) -> &str {
+        match self {
+            TextSpan::Text(text) => text,
+            TextSpan::Tribute { name, .. } => name,
+            TextSpan::Pronoun { text, .. } => text,
+            TextSpan::Item(item) => item
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct EventResult {
+    // the rendered text, the same as all of the spans joined together
     pub text: String,
-    pub killed: Vec<usize>,
-    pub killers: Vec<usize>,
+    pub spans: Vec<TextSpan>,
+    pub killed: Vec<String>,
+    pub killers: Vec<String>,
+    pub assists: Vec<String>,
+    pub injured: Vec<String>,
+    pub allies: Vec<String>,
+    // the rendered cause of death, or the text if the event doesn't
+    // have one. Empty if nobody died
+    pub cause: String,
     pub tributes: Vec<Tribute>
 }
 
@@ -69,33 +212,181 @@ impl DataTrait for Event {
     type Output = Event;
 
     fn from_file(file: &str) -> Result<Event, FileError> {
-        // Attempt to read the data in a file
-        let contents = std::fs::read_to_string(file).map_err(|source|
-            FileError::FileReadError {
-                file: file.to_string(),
-                source
-            }
-        )?;
+        let mut event: Event = read_data_file(file)?;
+        event.load_script(file)?;
 
-        // Attempt to parse the data to TOML
-        let toml = toml::from_str(&contents).map_err(|source|
-            FileError::TOMLParseError {
-                file: file.to_string(),
-                source
-            }
-        )?;
-
-        Ok(toml)
+        Ok(event)
     }
 
     fn set_path(&mut self, file_name: &str) {
-        self.file_name = file_name.to_string().replace(".toml", "");
+        let path = Path::new(file_name);
+
+        self.file_name = get_data_name(path);
+        self.format = DataFormat::from_path(path).unwrap_or_default();
     }
 }
 
 impl Event {
+    // Compiles the event's script, if it has one. `file` is
+    // the event file, which the script is found next to
+    pub fn load_script(&mut self, file: &str) -> Result<(), FileError> {
+        self.compiled_script = match self.script.is_empty() {
+            true => None,
+            false => Some(EventScript::load(file, &self.script)?)
+        };
+
+        for follow_up in &mut self.follow_ups {
+            follow_up.load_script(file)?;
+        }
+
+        Ok(())
+    }
+
+    // The event and all of its follow-ups, and theirs, and so on.
+    // Follow-ups get the event's file name, if they don't have one
+    pub fn with_follow_ups(&self) -> Vec<Event> {
+        let mut events = vec![ self.clone() ];
+
+        for follow_up in &self.follow_ups {
+            let mut follow_up = follow_up.clone();
+
+            if follow_up.file_name.is_empty() {
+                follow_up.file_name = self.file_name.clone();
+            }
+
+            events.append(&mut follow_up.with_follow_ups());
+        }
+
+        events
+    }
+
+    // Makes sure every follow-up, and their follow-ups, only use players
+    // from the event they follow, and happen in a phase that picks them up
+    pub fn check_follow_ups(&self) -> Result<(), EventError> {
+        let players = self.get_player_numbers();
+
+        for follow_up in &self.follow_ups {
+            if !matches!(follow_up.category, EventCategory::Bloodbath | EventCategory::Day | EventCategory::Night) {
+                return Err(EventError::InvalidFollowUpCategory {
+                    event: follow_up.text.clone(),
+                    category: follow_up.category.clone()
+                });
+            }
+
+            // outcomes can bring in players of their own
+            for variant in follow_up.get_variants() {
+                if let Some(player_num) = variant.get_player_numbers().into_iter().find(|num| !players.contains(num)) {
+                    return Err(EventError::UnknownFollowUpPlayer {
+                        event: variant.text,
+                        player_num,
+                        players
+                    });
+                }
+            }
+
+            follow_up.check_follow_ups()?;
+        }
+
+        Ok(())
+    }
+
+    // Writes the event to `dir`, in the format it was loaded from.
+    // Returns the path of the file
+    pub fn save(&self, dir: &str) -> Result<String, FileError> {
+        let path = Path::new(dir)
+            .join(format!("{}.{}", self.file_name, self.format.get_extension()))
+            .to_string_lossy()
+            .to_string();
+
+        write_data_file(&path, self, self.format)?;
+
+        Ok(path)
+    }
+
     pub fn get_text(&self) -> String { self.text.clone() }
 
+    // The event once for every outcome, as if each was its own event, with
+    // the event's weight shared out between them. Events without any
+    // outcomes are their own only variant
+    pub fn get_variants(&self) -> Vec<Event> {
+        if self.outcomes.is_empty() {
+            return vec![ self.clone() ];
+        }
+
+        let total_weight = self.outcomes.iter().map(|outcome| outcome.weight.max(0)).sum::<i32>();
+
+        self.outcomes.iter().map(|outcome| {
+            let mut variant = self.clone();
+            variant.outcomes = Vec::new();
+
+            if !outcome.text.is_empty() {
+                variant.text = format!("{} {}", self.text, outcome.text);
+            }
+
+            for player in &outcome.killed {
+                if !variant.killed.contains(player) {
+                    variant.killed.push(player.clone());
+                }
+            }
+
+            for player in &outcome.killers {
+                if !variant.killers.contains(player) {
+                    variant.killers.push(player.clone());
+                }
+            }
+
+            if !outcome.cause.is_empty() {
+                variant.cause = outcome.cause.clone();
+            }
+
+            for player in &outcome.assists {
+                if !variant.assists.contains(player) {
+                    variant.assists.push(player.clone());
+                }
+            }
+
+            for player in &outcome.injured {
+                if !variant.injured.contains(player) {
+                    variant.injured.push(player.clone());
+                }
+            }
+
+            for player in &outcome.allies {
+                if !variant.allies.contains(player) {
+                    variant.allies.push(player.clone());
+                }
+            }
+
+            variant.weight = match (outcome.weight, total_weight) {
+                (weight, total) if weight <= 0 || total <= 0 || self.weight <= 0 => 0,
+                // rare outcomes of rare events still get a chance
+                (weight, total) => ((self.weight as f64 * weight as f64 / total as f64).round() as i32).max(1)
+            };
+
+            variant
+        }).collect()
+    }
+
+    // The text of a FallenTributes event, for `count` fallen tributes.
+    // (count) is the number, or "No" if nobody fell, and
+    // (count)(shot/shots) picks a word like (P1)(is/are) does
+    pub fn get_fallen_text(&self, count: usize) -> String {
+        let word_regex = Regex::new(r"(?i)\(count\)\((?P<singular>[^/)]+)/(?P<plural>[^)]+)\)").unwrap();
+        let count_regex = Regex::new(r"(?i)\(count\)").unwrap();
+
+        let text = word_regex.replace_all(&self.text, |cap: &Captures| match count {
+            1 => cap["singular"].to_string(),
+            _ => cap["plural"].to_string()
+        });
+
+        let count_text = match count {
+            0 => String::from("No"),
+            _ => count.to_string()
+        };
+
+        count_regex.replace_all(&text, count_text.as_str()).to_string()
+    }
+
     pub fn get_num_tributes_required(&self) -> usize {
         let reg_expr_player = Regex::new(r"(?i)\(P[1-9][^)]*\)").unwrap();
 
@@ -109,8 +400,58 @@ impl Event {
         tribute_collection.len()
     }
 
-    // TODO: separate this function into other functions
-    pub fn get_result(&mut self, tributes: &mut Vec<Tribute>) -> Result<EventResult, EventError> {
+    // Picks tributes for the event and works out what happens to them.
+    // Events with a script can turn down the tributes they're given, in
+    // which case `None` is returned and `tributes` is left as it was
+    pub fn get_result(&mut self, tributes: &mut Vec<Tribute>, rng: &mut impl Rng, context: &ScriptContext) -> Result<Option<EventResult>, EventError> {
+        let mut tribute_collection: HashMap<String, Tribute> = HashMap::new();
+        let mut tributes_left = tributes.clone();
+
+        // choose a random tribute for each player (e.g. (P1), (P2), (P3), etc)
+        for player_val in self.get_player_vals()? {
+            let index = rng.gen_range(0..tributes_left.len());
+            tribute_collection.insert(player_val, tributes_left.remove(index));
+        }
+
+        let result = self.resolve(tribute_collection, context)?;
+
+        if result.is_some() {
+            *tributes = tributes_left;
+        }
+
+        Ok(result)
+    }
+
+    // Works out what happens to a cast that's already been picked, e.g.
+    // for a follow-up. (P1) is cast[&1], (P3) is cast[&3] and so on
+    pub fn get_result_for(&mut self, cast: &HashMap<u32, Tribute>, context: &ScriptContext) -> Result<Option<EventResult>, EventError> {
+        let mut tribute_collection: HashMap<String, Tribute> = HashMap::new();
+
+        for player_val in self.get_player_vals()? {
+            let trib = cast.get(&get_tribute_num(&player_val)).ok_or(EventError::MissingPlayerIdentifier {
+                event: self.text.clone(),
+                player_num: get_tribute_num(&player_val) as i32
+            })?;
+
+            tribute_collection.insert(player_val, trib.clone());
+        }
+
+        self.resolve(tribute_collection, context)
+    }
+
+    // The numbers of the players in the text, e.g. [1, 2] for (P1) and (P2)
+    pub fn get_player_numbers(&self) -> Vec<u32> {
+        self.get_player_vals()
+            .unwrap_or_default()
+            .iter()
+            .map(|player_val| get_tribute_num(player_val))
+            .sorted()
+            .dedup()
+            .collect()
+    }
+
+    // Every player in the text, e.g. (P1), in the order they first show up
+    fn get_player_vals(&self) -> Result<Vec<String>, EventError> {
         // match ex: (P1)
         let player_regex = r"(?i)\(P[1-9][^)]*\)";
         let reg_expr_player = Regex::new(player_regex).map_err(|source|
@@ -120,61 +461,58 @@ impl Event {
             }
         )?;
 
-        let they_regex = r"(?i)\(they[1-9][^)]*\)";
-        let reg_expr_they = Regex::new(they_regex).map_err(|source|
-            EventError::RegexInvalid {
-                regex: they_regex.to_string(),
-                source
-            }
-        )?;
+        let mut player_vals: Vec<String> = Vec::new();
 
-        let them_regex = r"(?i)\(them[1-9][^)]*\)";
-        let reg_expr_them = Regex::new(them_regex).map_err(|source|
-            EventError::RegexInvalid {
-                regex: them_regex.to_string(),
-                source
+        for player_cap in reg_expr_player.captures_iter(&self.text) {
+            if !player_vals.contains(&player_cap[0].to_uppercase()) {
+                player_vals.push(player_cap[0].to_uppercase());
             }
-        )?;
+        }
 
-        let their_regex = r"(?i)\(their[1-9][^)]*\)";
-        let reg_expr_their = Regex::new(their_regex).map_err(|source|
-            EventError::RegexInvalid {
-                regex: them_regex.to_string(),
-                source
-            }
-        )?;
+        Ok(player_vals)
+    }
 
-        let themself_regex = r"(?i)\(themself[1-9][^)]*\)";
-        let reg_expr_themself = Regex::new(themself_regex).map_err(|source|
+    fn resolve(&self, mut tribute_collection: HashMap<String, Tribute>, context: &ScriptContext) -> Result<Option<EventResult>, EventError> {
+        // match ex: (P1)(is/are), (P1), (their1) or (item:spear)
+        let token_regex = r"(?i)(?P<word_player>\(P[1-9][^)]*\))\((?P<singular>[0-9a-zA-Z'_0\-& ]+)/(?P<plural>[0-9a-zA-Z'_0\-&]+)\)|(?P<player>\(P[1-9][^)]*\))|(?P<pronoun>\((?:themself|they|their|them)[1-9][^)]*\))|\(item:(?P<item>[^)]+)\)";
+        let reg_expr_token = Regex::new(token_regex).map_err(|source|
             EventError::RegexInvalid {
-                regex: themself_regex.to_string(),
+                regex: token_regex.to_string(),
                 source
             }
         )?;
 
-        let parenthesis_regex = r"(?i)\(P[1-9][^)]*\)\([0-9a-zA-Z'_0\-& ]+/[0-9a-zA-Z'_0\-&]+\)";
-        let reg_exp_parenthesis = Regex::new(parenthesis_regex).map_err(|source|
-            EventError::RegexInvalid {
-                regex: parenthesis_regex.to_string(),
-                source
-            }
-        )?;
-        
-        let mut tribute_collection: HashMap<String, Tribute> = HashMap::new();
+        let (text, killed, killers) = match &self.compiled_script {
+            Some(script) => {
+                let players = tribute_collection.iter()
+                    .map(|(player_val, trib)| (get_tribute_num(player_val), trib.clone()))
+                    .sorted_by_key(|(num, _)| *num)
+                    .collect::<Vec<(u32, Tribute)>>();
 
-        /* Iterate over the player captures (e.g. (P1), (P2), (P3), etc).
-         * If the player capture isn't in our tribute collection, choose a random tribute
-         * and add them to the collection */
-        for player_cap in reg_expr_player.captures_iter(&self.text) {
-            if !tribute_collection.contains_key(&player_cap[0].to_uppercase()) {
-                let index = rand::thread_rng().gen_range(0..tributes.len());
-                let trib = tributes.remove(index);
+                let outcome = script.run(&self.text, &players, &get_player_nums(&self.killed), &get_player_nums(&self.killers), context)
+                    .map_err(|message| EventError::ScriptError {
+                        event: self.text.clone(),
+                        message
+                    })?;
 
-                tribute_collection.insert(String::from(&player_cap[0].to_uppercase()), trib.clone());
-            }
-        }
+                if !outcome.eligible {
+                    return Ok(None);
+                }
+
+                for ((num, _), kills) in players.iter().zip(outcome.kills) {
+                    if let Some(trib) = tribute_collection.get_mut(&format!("(P{})", num)) {
+                        trib.kills = kills;
+                    }
+                }
 
-        let mut result = EventResult::new(&self.text);
+                let to_players = |nums: Vec<u32>| nums.iter().map(|num| format!("(P{})", num)).collect::<Vec<String>>();
+
+                (outcome.text, to_players(outcome.killed), to_players(outcome.killers))
+            },
+            None => (self.text.clone(), self.killed.clone(), self.killers.clone())
+        };
+
+        let mut result = EventResult::new(&text);
 
         // iterate over the collection of tributes,
         // updating the "alive" status of the ones
@@ -183,28 +521,114 @@ impl Event {
             .sorted_by(|a, b|
                 get_tribute_num(&a.0).cmp(&get_tribute_num(&b.0))) {
             
-            if self.killed.contains(&player_val) {
+            if killed.contains(&player_val) {
                 tribute.kill();
-                result.killed.push(tribute.get_id());    
+                result.killed.push(tribute.get_id().to_string());    
             }
             
-            if self.killers.contains(&player_val) {
-                result.killers.push(tribute.get_id());
+            if killers.contains(&player_val) {
+                result.killers.push(tribute.get_id().to_string());
+            }
+
+            if self.assists.contains(&player_val) && !killers.contains(&player_val) {
+                result.assists.push(tribute.get_id().to_string());
+            }
+
+            if self.injured.contains(&player_val) {
+                result.injured.push(tribute.get_id().to_string());
+            }
+
+            if self.allies.contains(&player_val) {
+                result.allies.push(tribute.get_id().to_string());
             }
 
             result.tributes.push(tribute);
         }
 
-        if result.killers.len() > 0 && result.killed.len() == 0 || result.killed.len() > 0 && result.killers.len() == 0 {
-            return Err(EventError::MissingFieldsError { event: result.text })
+        // deaths don't need a killer (falling out of a tree, say),
+        // but killers and assists need someone to have died
+        if (!result.killers.is_empty() || !result.assists.is_empty()) && result.killed.is_empty() {
+            return Err(EventError::MissingFieldsError { event: text })
+        }
+
+        result.spans = self.get_spans(&text, &reg_expr_token, &tribute_collection)?;
+        result.text = result.spans.iter().map(|span| span.get_text()).collect();
+        result.cause = self.get_spans(&self.cause, &reg_expr_token, &tribute_collection)?
+            .iter()
+            .map(|span| span.get_text())
+            .collect();
+
+        if result.cause.is_empty() && !result.killed.is_empty() {
+            result.cause = result.text.trim_end().to_string();
+        }
+
+        Ok(Some(result))
+    }
+
+    // Splits the event text into spans, swapping
+    // tokens out for the tributes they refer to
+    fn get_spans(&self, text: &str, token_regex: &Regex, tribute_collection: &HashMap<String, Tribute>) -> Result<Vec<TextSpan>, EventError> {
+        let get_tribute = |token: &str| {
+            let num = get_tribute_num(token);
+
+            tribute_collection.iter()
+                .find(|(player_val, _)| get_tribute_num(player_val) == num)
+                .map(|(_, trib)| trib)
+                .ok_or(EventError::MissingPlayerIdentifier {
+                    event: text.to_string(),
+                    player_num: num as i32
+                })
+        };
+
+        let mut spans = Vec::new();
+        let mut last_end = 0;
+
+        for cap in token_regex.captures_iter(text) {
+            let token = cap.get(0).unwrap();
+
+            if token.start() > last_end {
+                spans.push(TextSpan::Text(text[last_end..token.start()].to_string()));
+            }
+            last_end = token.end();
+
+            let span = if let Some(player) = cap.name("word_player") {
+                let trib = get_tribute(player.as_str())?;
+
+                let word = if trib.pronouns == Pronouns::They {
+                    &cap["plural"]
+                } else {
+                    &cap["singular"]
+                };
+
+                TextSpan::Pronoun { tribute: trib.get_id().to_string(), text: word.to_string() }
+            } else if let Some(player) = cap.name("player") {
+                let trib = get_tribute(player.as_str())?;
+
+                TextSpan::Tribute { id: trib.get_id().to_string(), name: trib.name.clone() }
+            } else if let Some(pronoun) = cap.name("pronoun") {
+                let trib = get_tribute(pronoun.as_str())?;
+
+                // bleh
+                let lower_capture = pronoun.as_str().to_lowercase();
+                let text =
+                    if lower_capture.contains("they") { they(&trib.pronouns, pronoun.as_str().to_string()) }
+                    else if lower_capture.contains("themself") { themself(&trib.pronouns, pronoun.as_str().to_string()) }
+                    else if lower_capture.contains("their") { their(&trib.pronouns, pronoun.as_str().to_string()) }
+                    else { them(&trib.pronouns, pronoun.as_str().to_string()) };
+
+                TextSpan::Pronoun { tribute: trib.get_id().to_string(), text }
+            } else {
+                TextSpan::Item(cap["item"].to_string())
+            };
+
+            spans.push(span);
+        }
+
+        if last_end < text.len() {
+            spans.push(TextSpan::Text(text[last_end..].to_string()));
         }
 
-        result.replace_pronouns(reg_expr_they)?
-              .replace_pronouns(reg_expr_them)?
-              .replace_pronouns(reg_expr_their)?
-              .replace_pronouns(reg_expr_themself)?
-              .replace_player_words(reg_exp_parenthesis, reg_expr_player)?
-              .replace_players(tribute_collection)
+        Ok(spans)
     }
 }
 
@@ -212,89 +636,182 @@ impl EventResult {
     pub fn new(text: &str) -> EventResult {
         EventResult {
             text: String::from(text),
+            spans: vec![TextSpan::Text(String::from(text))],
             killed: Vec::new(),
             killers: Vec::new(),
+            assists: Vec::new(),
+            injured: Vec::new(),
+            allies: Vec::new(),
+            cause: String::new(),
             tributes: Vec::new()
         }
     }
 
-    pub fn replace_players(self, tribute_collection: HashMap<String, Tribute>) -> Result<EventResult, EventError> {
-        let mut result = self.clone();
-        
-        for (player_val, tribute) in tribute_collection.into_iter()
-            .sorted_by(|a, b|
-                get_tribute_num(&a.0).cmp(&get_tribute_num(&b.0))) {
-            let e = tribute.name.clone();
-            result.text = result.text.replace(&player_val, &e);
+    // Builds a result out of spans, rendering them into its text
+    pub fn from_spans(spans: Vec<TextSpan>) -> EventResult {
+        EventResult {
+            text: spans.iter().map(|span| span.get_text()).collect(),
+            spans,
+            killed: Vec::new(),
+            killers: Vec::new(),
+            assists: Vec::new(),
+            injured: Vec::new(),
+            allies: Vec::new(),
+            cause: String::new(),
+            tributes: Vec::new()
         }
+    }
+}
 
-        Ok(result)
+// e.g. [ "(P1)", "(P3)" ] -> [ 1, 3 ], skipping anything that isn't a player
+fn get_player_nums(players: &[String]) -> Vec<u32> {
+    players
+        .iter()
+        .filter_map(|player| player.trim().trim_start_matches(['(', 'P', 'p']).trim_end_matches(')').parse().ok())
+        .collect()
+}
+
+fn get_tribute_num(string: &str) -> u32 {
+    string.chars().rev().nth(1).unwrap().to_digit(10).unwrap()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(toml: &str) -> Event {
+        toml::from_str(toml).unwrap()
     }
-    pub fn replace_player_words(self, word_regex: Regex, player_regex: Regex) -> Result<EventResult, EventError> {
-        let mut result = self.clone();
 
-        // iterate over matches, replace them w/the tributes pronouns
-        for word_cap in word_regex.captures_iter(&self.text) {
-            let mut words = word_cap[0].to_string();
+    #[test]
+    fn outcomes_share_the_weight() {
+        let event = event(r#"
+            text = "(P1) finds a river."
+            category = "Day"
+            weight = 60
+
+            [[outcomes]]
+            text = "(P1) drinks from it."
+            weight = 2
+
+            [[outcomes]]
+            text = "(P1) drowns."
+            killed = [ "(P1)" ]
+            weight = 1
+        "#);
+
+        let variants = event.get_variants();
+
+        assert_eq!(variants.len(), 2);
+        assert_eq!(variants[0].weight, 40);
+        assert_eq!(variants[1].weight, 20);
+        assert_eq!(variants[1].text, "(P1) finds a river. (P1) drowns.");
+        assert_eq!(variants[1].killed, vec![ "(P1)" ]);
+        assert!(variants.iter().all(|variant| variant.outcomes.is_empty()));
+    }
 
-            for player_cap in player_regex.captures_iter(&words.clone()) {
-                let trib_id = get_tribute_num(&player_cap[0]);
+    #[test]
+    fn rare_outcomes_still_get_a_chance() {
+        let event = event(r#"
+            text = "(P1) finds a river."
+            category = "Day"
+            weight = 1
 
-                let trib = self.tributes.get((trib_id - 1) as usize).ok_or(EventError::MissingPlayerIdentifier {
-                    event: self.text.clone(),
-                    player_num: (trib_id - 1) as i32
-                })?;
+            [[outcomes]]
+            weight = 99
 
-                // eh?
-                words = words.replace(&player_cap[0], "")
-                    .replace("(", "")
-                    .replace(")", "");
-                
-                let words_split = words.split('/').collect_vec();
+            [[outcomes]]
+            weight = 1
 
-                let word = if trib.pronouns == Pronouns::They {
-                    words_split[1]
-                } else {
-                    words_split[0]
-                };
+            [[outcomes]]
+            weight = 0
+        "#);
 
-                result.text = result.text.replace(&word_cap[0], word)
-            }
-        }
+        let weights = event.get_variants().iter().map(|variant| variant.weight).collect::<Vec<i32>>();
 
-        Ok(result)
+        assert_eq!(weights, vec![ 1, 1, 0 ]);
     }
 
-    pub fn replace_pronouns(self, regex: Regex) -> Result<EventResult, EventError> {
-        let mut result = self.clone();
+    #[test]
+    fn events_without_outcomes_are_their_own_variant() {
+        let event = event("text = \"(P1) naps.\"\ncategory = \"Day\"");
 
-        // iterate over matches, replace them w/the tributes pronouns
-        for cap in regex.captures_iter(&self.text) {
-            let trib_id = get_tribute_num(&cap[0]);
+        assert_eq!(event.get_variants(), vec![ event.clone() ]);
+    }
 
-            let trib = self.tributes.get((trib_id - 1) as usize).ok_or(EventError::MissingPlayerIdentifier {
-                event: self.text.clone(),
-                player_num: (trib_id - 1) as i32
-            })?;
-            
-            // bleh
-            let lower_capture = cap[0].to_lowercase();
-            let prns =
-                if lower_capture.contains("they") { Option::Some(they(&trib.pronouns, cap[0].to_string())) }
-                else if lower_capture.contains("themself") { Option::Some(themself(&trib.pronouns, cap[0].to_string())) }
-                else if lower_capture.contains("their") { Option::Some(their(&trib.pronouns, cap[0].to_string())) }
-                else if lower_capture.contains("them") { Option::Some(them(&trib.pronouns, cap[0].to_string())) }
-                else { Option::None };
-    
-            if let Option::Some(s) = prns {
-                result.text = result.text.replace(&cap[0], &s);
-            }
+    #[test]
+    fn follow_ups_only_use_the_players_they_follow() {
+        let valid = event(r#"
+            text = "(P1) trips (P3)."
+            category = "Day"
+
+            [[follow_ups]]
+            text = "(P3) gets back at (P1)."
+            category = "Night"
+
+            [[follow_ups.follow_ups]]
+            text = "(P3) sleeps it off."
+            category = "Day"
+        "#);
+
+        let invalid = event(r#"
+            text = "(P1) trips (P3)."
+            category = "Day"
+
+            [[follow_ups]]
+            text = "(P1) and (P3) make up."
+            category = "Night"
+
+            [[follow_ups.follow_ups]]
+            text = "(P2) laughs at them."
+            category = "Day"
+        "#);
+
+        assert!(valid.check_follow_ups().is_ok());
+        assert!(matches!(
+            invalid.check_follow_ups(),
+            Err(EventError::UnknownFollowUpPlayer { player_num: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn follow_ups_need_a_phase_that_picks_them_up() {
+        let event = event(r#"
+            text = "(P1) hears a cannon."
+            category = "Day"
+
+            [[follow_ups]]
+            text = "(P1) is listed."
+            category = "FallenTributes"
+        "#);
+
+        assert!(matches!(
+            event.check_follow_ups(),
+            Err(EventError::InvalidFollowUpCategory { category: EventCategory::FallenTributes, .. })
+        ));
+    }
+
+    #[test]
+    fn follow_ups_keep_their_player_numbers() {
+        let mut follow_up = event("text = \"(P3) gets back at (P1).\"\ncategory = \"Night\"");
+
+        let mut cast = HashMap::new();
+        for (num, name) in [ (1, "Katniss"), (3, "Cato") ] {
+            let mut trib = Tribute::new(name, "", Pronouns::She);
+            trib.set_id(name);
+            cast.insert(num, trib);
         }
 
-        Ok(result)
+        let result = follow_up.get_result_for(&cast, &ScriptContext::default()).unwrap().unwrap();
+
+        assert_eq!(result.text, "Cato gets back at Katniss.");
     }
-}
 
-fn get_tribute_num(string: &str) -> u32 {
-    string.chars().rev().nth(1).unwrap().to_digit(10).unwrap()
-}
\ No newline at end of file
+    #[test]
+    fn fallen_text_counts_the_fallen() {
+        let event = event("text = \"(count) cannon (count)(shot/shots) can be heard.\"\ncategory = \"FallenTributes\"");
+
+        assert_eq!(event.get_fallen_text(0), "No cannon shots can be heard.");
+        assert_eq!(event.get_fallen_text(1), "1 cannon shot can be heard.");
+        assert_eq!(event.get_fallen_text(3), "3 cannon shots can be heard.");
+    }
+}
@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use serde::{Serialize, Deserialize, Deserializer, de::IgnoredAny};
+
+use crate::{event::Event, data_trait::{DataTrait, DataFormat, FileError, read_data_file, write_data_file, get_data_name}};
+
+// Many events in one file, along with some info about them, e.g.
+//
+// name = "Classic"
+// author = "someone"
+// content_warnings = [ "gore" ]
+//
+// [[events]]
+// text = "(P1) takes a nap."
+// category = "Day"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct EventPack {
+    #[serde(skip)]
+    pub file_name: String,
+    #[serde(skip)]
+    pub format: DataFormat,
+    // single event files are loaded as a pack holding just that event
+    #[serde(skip)]
+    pub is_single: bool,
+
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub author: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub description: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_warnings: Vec<String>,
+    pub events: Vec<Event>
+}
+
+// Just enough of a file to tell whether or not it's a pack
+#[derive(Deserialize)]
+struct PackCheck {
+    #[serde(default, deserialize_with = "is_present")]
+    events: bool
+}
+
+// Not an Option, since RON would want the events wrapped in Some(...)
+fn is_present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    IgnoredAny::deserialize(deserializer).map(|_| true)
+}
+
+impl DataTrait for EventPack {
+    type Output = EventPack;
+
+    fn from_file(file: &str) -> Result<EventPack, FileError> {
+        // anything with a list of events is a pack, everything else is a
+        // single event. Checking first means parse errors are about the right one
+        if read_data_file::<PackCheck>(file)?.events {
+            let mut pack: EventPack = read_data_file(file)?;
+
+            for event in &mut pack.events {
+                event.load_script(file)?;
+            }
+
+            return Ok(pack);
+        }
+
+        Ok(EventPack {
+            file_name: String::new(),
+            format: DataFormat::default(),
+            is_single: true,
+            name: String::new(),
+            author: String::new(),
+            version: String::new(),
+            description: String::new(),
+            content_warnings: Vec::new(),
+            events: vec![ Event::from_file(file)? ]
+        })
+    }
+
+    fn set_path(&mut self, file_name: &str) {
+        let path = Path::new(file_name);
+
+        self.file_name = get_data_name(path);
+        self.format = DataFormat::from_path(path).unwrap_or_default();
+
+        if self.is_single {
+            for event in &mut self.events {
+                event.set_path(file_name);
+            }
+
+            return;
+        }
+
+        // e.g. "classic#3" for the third event in classic.toml, so
+        // errors and skipped events can be traced back to the pack
+        for (i, event) in self.events.iter_mut().enumerate() {
+            event.file_name = format!("{}#{}", self.file_name, i + 1);
+            event.format = self.format;
+        }
+    }
+}
+
+impl EventPack {
+    // The pack's name, or its file name if it doesn't have one
+    pub fn get_name(&self) -> &str {
+        if self.name.is_empty() {
+            &self.file_name
+        } else {
+            &self.name
+        }
+    }
+
+    // e.g. "Classic 1.0 by someone"
+    pub fn get_title(&self) -> String {
+        let mut title = String::from(self.get_name());
+
+        if !self.version.is_empty() {
+            title.push_str(&format!(" {}", self.version));
+        }
+
+        if !self.author.is_empty() {
+            title.push_str(&format!(" by {}", self.author));
+        }
+
+        title
+    }
+
+    // Writes the pack to `dir`, in the format it was loaded from.
+    // Returns the path of the file
+    pub fn save(&self, dir: &str) -> Result<String, FileError> {
+        let path = Path::new(dir)
+            .join(format!("{}.{}", self.file_name, self.format.get_extension()))
+            .to_string_lossy()
+            .to_string();
+
+        write_data_file(&path, self, self.format)?;
+
+        Ok(path)
+    }
+}
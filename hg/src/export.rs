@@ -0,0 +1,276 @@
+use std::{path::Path, str::FromStr};
+
+use serde::Serialize;
+
+use crate::{simulation::Simulation, data_trait::FileError, event::{EventCategory, EventResult, TextSpan}, summary::GameSummary, timeline::TimelineEntry};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ExportFormat {
+    Text,
+    Json,
+    Html
+}
+
+impl ExportFormat {
+    // Picks a format based on the file extension, falling back to text
+    pub fn from_path(path: &str) -> Self {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("");
+
+        ext.parse().unwrap_or(ExportFormat::Text)
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(ExportFormat::Text),
+            "json" => Ok(ExportFormat::Json),
+            "html" | "htm" => Ok(ExportFormat::Html),
+            _ => Err(format!("Unknown export format `{}`, expected one of: text, json, html", s))
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct PhaseExport<'a> {
+    title: &'a str,
+    category: &'a EventCategory,
+    day: u32,
+    events: Vec<&'a str>
+}
+
+#[derive(Serialize)]
+struct TributeExport<'a> {
+    id: &'a str,
+    name: &'a str,
+    district: &'a str,
+    timeline: &'a [TimelineEntry]
+}
+
+#[derive(Serialize)]
+struct LogExport<'a> {
+    seed: u64,
+    phases: Vec<PhaseExport<'a>>,
+    winners: Vec<&'a str>,
+    summary: Option<GameSummary>,
+    tributes: Vec<TributeExport<'a>>
+}
+
+pub fn export(sim: &Simulation, format: ExportFormat) -> Result<String, serde_json::Error> {
+    match format {
+        ExportFormat::Text => Ok(export_text(sim)),
+        ExportFormat::Json => export_json(sim),
+        ExportFormat::Html => Ok(export_html(sim))
+    }
+}
+
+// The whole game as plain text: every phase, then the
+// summary once the games are over
+pub fn export_text(sim: &Simulation) -> String {
+    let mut text = format!("Seed: {}\n\n", sim.get_seed());
+
+    for phase in sim.get_history() {
+        text.push_str(&format!("===== {}\n", phase.title));
+
+        for event in &phase.events {
+            text.push_str(&format!("{}\n", event.text.trim_end()));
+        }
+
+        text.push('\n');
+    }
+
+    if sim.is_end() {
+        if let Some(victory) = sim.get_victory_text() {
+            text.push_str(&format!("{}\n\n", victory));
+        }
+
+        text.push_str(&sim.get_summary().to_text());
+        text.push('\n');
+    }
+
+    text.push_str("===== Tributes\n");
+
+    for dist in sim.get_districts() {
+        for trib in &dist.tributes {
+            text.push_str(&format!("\n{} ({})\n", trib.name, dist.name));
+
+            for entry in sim.get_timeline(trib.get_id()) {
+                text.push_str(&format!("  {}: {}\n", entry.title, entry.text));
+
+                for fact in &entry.facts {
+                    text.push_str(&format!("    - {}\n", fact.get_text(sim)));
+                }
+            }
+        }
+    }
+
+    text
+}
+
+pub fn export_json(sim: &Simulation) -> Result<String, serde_json::Error> {
+    let districts = sim.get_districts();
+
+    let log = LogExport {
+        seed: sim.get_seed(),
+        phases: sim.get_history()
+            .iter()
+            .map(|phase| PhaseExport {
+                title: &phase.title,
+                category: &phase.category,
+                day: phase.day,
+                events: phase.events.iter().map(|event| event.text.trim_end()).collect()
+            })
+            .collect(),
+        winners: if sim.is_end() {
+            sim.get_winners().iter().map(|trib| trib.name.as_str()).collect()
+        } else {
+            Vec::new()
+        },
+        summary: if sim.is_end() { Some(sim.get_summary()) } else { None },
+        tributes: districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter().map(move |trib| TributeExport {
+                id: trib.get_id(),
+                name: &trib.name,
+                district: &dist.name,
+                timeline: sim.get_timeline(trib.get_id())
+            }))
+            .collect()
+    };
+
+    serde_json::to_string_pretty(&log)
+}
+
+// A standalone HTML page, no external stylesheets or scripts
+pub fn export_html(sim: &Simulation) -> String {
+    let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Hunger Games Simulator</title>\n</head>\n<body>\n");
+    html.push_str("<h1>Hunger Games Simulator</h1>\n");
+    html.push_str(&format!("<p>Seed: {}</p>\n", sim.get_seed()));
+
+    for phase in sim.get_history() {
+        html.push_str(&format!("<h2>{}</h2>\n", escape_html(&phase.title)));
+
+        for event in &phase.events {
+            html.push_str(&format!("<p>{}</p>\n", html_event(event)));
+        }
+    }
+
+    if sim.is_end() {
+        let summary = sim.get_summary();
+
+        if let Some(victory) = sim.get_victory_text() {
+            html.push_str(&format!("<h2>{}</h2>\n", escape_html(&victory)));
+        }
+
+        html.push_str(&format!("<p>{}</p>\n", escape_html(&summary.get_days_text())));
+
+        html.push_str("<h3>Final Placements</h3>\n<table>\n");
+        for placement in &summary.placements {
+            html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                placement.place, escape_html(&placement.name), escape_html(&placement.district), placement.get_kills_text()));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h3>Kill Leaderboard</h3>\n<table>\n");
+        for placement in &summary.kill_leaderboard {
+            html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&placement.name), escape_html(&placement.district), placement.kills));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h3>Districts</h3>\n<table>\n<tr><th>District</th><th>Best Placement</th><th>Kills</th><th>Survivors</th></tr>\n");
+        for dist in &summary.districts {
+            html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&dist.name), dist.best_place, dist.kills, dist.survivors));
+        }
+        html.push_str("</table>\n");
+
+        html.push_str("<h3>Who Killed Whom</h3>\n<ul>\n");
+        for kill in &summary.kills {
+            html.push_str(&format!("<li>{}</li>\n", escape_html(&kill.get_text())));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    // a section for every tribute, which the names in events link to
+    html.push_str("<h2>Tributes</h2>\n");
+    for dist in sim.get_districts() {
+        html.push_str(&format!("<h3>{}</h3>\n", escape_html(&dist.name)));
+
+        for trib in &dist.tributes {
+            html.push_str(&format!("<h4 id=\"tribute-{}\">{}</h4>\n<ul>\n", escape_html(trib.get_id()), escape_html(&trib.name)));
+
+            for entry in sim.get_timeline(trib.get_id()) {
+                let event = match sim.get_history().get(entry.phase).and_then(|phase| phase.events.get(entry.event)) {
+                    Some(event) => event,
+                    None => continue
+                };
+
+                html.push_str(&format!("<li>{}: {}", escape_html(&entry.title), html_event(event)));
+
+                if !entry.facts.is_empty() {
+                    html.push_str("\n<ul>\n");
+                    for fact in &entry.facts {
+                        html.push_str(&format!("<li>{}</li>\n", escape_html(&fact.get_text(sim))));
+                    }
+                    html.push_str("</ul>\n");
+                }
+
+                html.push_str("</li>\n");
+            }
+
+            html.push_str("</ul>\n");
+        }
+    }
+
+    html.push_str("</body>\n</html>\n");
+
+    html
+}
+
+// Saves the game log, picking the format from the file extension
+pub fn save_log(sim: &Simulation, path: &str) -> Result<(), FileError> {
+    save_log_as(sim, path, ExportFormat::from_path(path))
+}
+
+pub fn save_log_as(sim: &Simulation, path: &str, format: ExportFormat) -> Result<(), FileError> {
+    let contents = export(sim, format).map_err(|source|
+        FileError::JSONSerializeError {
+            file: String::from(path),
+            source
+        }
+    )?;
+
+    std::fs::write(path, contents).map_err(|source|
+        FileError::FileWriteError {
+            file: String::from(path),
+            source
+        }
+    )
+}
+
+// Tribute names link to their section of the page
+fn html_event(event: &EventResult) -> String {
+    let html = event.spans
+        .iter()
+        .map(|span| match span {
+            TextSpan::Tribute { id, name } => format!("<a href=\"#tribute-{}\">{}</a>", escape_html(id), escape_html(name)),
+            TextSpan::Item(item) => format!("<em>{}</em>", escape_html(item)),
+            span => escape_html(span.get_text())
+        })
+        .collect::<String>();
+
+    html.trim_end().to_string()
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
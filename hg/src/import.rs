@@ -0,0 +1,196 @@
+use std::fmt;
+
+use hashbrown::HashMap;
+
+use crate::{district::District, tribute::Tribute, pronouns::Pronouns, data_trait::{DataTrait, FileError}};
+
+// A line that couldn't be imported, and why
+#[derive(Debug, Clone)]
+pub struct ImportProblem {
+    pub line: usize,
+    pub text: String,
+    pub reason: String
+}
+
+impl fmt::Display for ImportProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {} ({})", self.line, self.text, self.reason)
+    }
+}
+
+pub struct CastImport {
+    pub districts: Vec<District>,
+    pub problems: Vec<ImportProblem>
+}
+
+// Reads a cast from a spreadsheet exported as CSV. The first row names the
+// columns: "name", "district" and "pronouns" are needed, "avatar" is optional.
+// Districts can be numbers ("1" becomes "District 1") or full names, and
+// they're created in the order they first show up
+pub fn import_cast_csv(contents: &str) -> CastImport {
+    let mut districts: Vec<District> = Vec::new();
+    let mut problems = Vec::new();
+
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(contents.as_bytes());
+
+    let headers = match reader.headers() {
+        Ok(headers) => headers.clone(),
+        Err(err) => {
+            problems.push(ImportProblem {
+                line: 1,
+                text: String::new(),
+                reason: err.to_string()
+            });
+
+            return CastImport { districts, problems }
+        }
+    };
+
+    let column = |names: &[&str]| headers.iter().position(|header| names.contains(&header.to_lowercase().as_str()));
+
+    let name_col = column(&["name"]);
+    let district_col = column(&["district"]);
+    let pronouns_col = column(&["pronouns", "pronoun"]);
+    let avatar_col = column(&["avatar", "avatar_url", "image"]);
+
+    for (col, name) in [(name_col, "name"), (district_col, "district"), (pronouns_col, "pronouns")] {
+        if col.is_none() {
+            problems.push(ImportProblem {
+                line: 1,
+                text: headers.iter().collect::<Vec<&str>>().join(","),
+                reason: format!("there's no `{}` column", name)
+            });
+        }
+    }
+
+    if !problems.is_empty() {
+        return CastImport { districts, problems }
+    }
+
+    // lowercase name -> the line it was first seen on
+    let mut seen_names: HashMap<String, usize> = HashMap::new();
+
+    for record in reader.records() {
+        let record = match record {
+            Ok(record) => record,
+            Err(err) => {
+                problems.push(ImportProblem {
+                    line: err.position().map(|pos| pos.line() as usize).unwrap_or(0),
+                    text: String::new(),
+                    reason: err.to_string()
+                });
+                continue;
+            }
+        };
+
+        let line = record.position().map(|pos| pos.line() as usize).unwrap_or(0);
+        let get = |col: Option<usize>| col.and_then(|col| record.get(col)).unwrap_or("");
+
+        let problem = |reason: String| ImportProblem {
+            line,
+            text: record.iter().collect::<Vec<&str>>().join(","),
+            reason
+        };
+
+        let name = get(name_col);
+        let district = get(district_col);
+
+        if name.is_empty() {
+            problems.push(problem(String::from("the tribute has no name")));
+            continue;
+        }
+
+        if district.is_empty() {
+            problems.push(problem(format!("{} has no district", name)));
+            continue;
+        }
+
+        let pronouns = match get(pronouns_col).parse::<Pronouns>() {
+            Ok(pronouns) => pronouns,
+            Err(err) => {
+                problems.push(problem(err));
+                continue;
+            }
+        };
+
+        if let Some(first_line) = seen_names.get(&name.to_lowercase()) {
+            problems.push(problem(format!("there's already a tribute called {} on line {}", name, first_line)));
+            continue;
+        }
+        seen_names.insert(name.to_lowercase(), line);
+
+        let district_name = if district.chars().all(|c| c.is_ascii_digit()) {
+            format!("District {}", district)
+        } else {
+            district.to_string()
+        };
+
+        let tribute = Tribute::new(name, get(avatar_col), pronouns);
+
+        match districts.iter_mut().find(|dist| dist.name == district_name) {
+            Some(dist) => dist.tributes.push(tribute),
+            None => districts.push(District::new(&district_name, vec![ tribute ]))
+        }
+    }
+
+    for (i, dist) in districts.iter_mut().enumerate() {
+        dist.set_path(&format!("district_{}.toml", i + 1));
+    }
+
+    CastImport { districts, problems }
+}
+
+// Writes the districts to `dir`, creating it if needed.
+// Returns the paths of the files
+pub fn save_districts(districts: &[District], dir: &str) -> Result<Vec<String>, FileError> {
+    std::fs::create_dir_all(dir).map_err(|source|
+        FileError::FileWriteError {
+            file: dir.to_string(),
+            source
+        }
+    )?;
+
+    districts.iter().map(|dist| dist.save(dir)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_tributes_by_district() {
+        let cast = import_cast_csv("Name,District,Pronouns,Avatar\nKatniss,12,she,katniss.png\nRue,11,she,\nPeeta,12,he,\nFinnick,Fishing,they,");
+
+        let names = cast.districts.iter().map(|dist| dist.name.as_str()).collect::<Vec<&str>>();
+
+        assert_eq!(names, vec![ "District 12", "District 11", "Fishing" ]);
+        assert_eq!(cast.districts[0].tributes.len(), 2);
+        assert_eq!(cast.districts[0].tributes[0].avatar_url, "katniss.png");
+        assert!(cast.districts[0].tributes[1].pronouns == Pronouns::He);
+        assert!(cast.districts[2].tributes[0].pronouns == Pronouns::They);
+        assert!(cast.problems.is_empty());
+    }
+
+    #[test]
+    fn reports_duplicates_and_bad_pronouns() {
+        let cast = import_cast_csv("name,district,pronouns\nKatniss,12,she\nkatniss,11,she\nRue,11,xyz");
+
+        assert_eq!(cast.districts.len(), 1);
+        assert_eq!(cast.problems.len(), 2);
+        assert_eq!(cast.problems[0].line, 3);
+        assert!(cast.problems[0].reason.contains("line 2"));
+        assert_eq!(cast.problems[1].line, 4);
+    }
+
+    #[test]
+    fn needs_every_column() {
+        let cast = import_cast_csv("name,pronouns\nKatniss,she");
+
+        assert!(cast.districts.is_empty());
+        assert_eq!(cast.problems.len(), 1);
+        assert!(cast.problems[0].reason.contains("district"));
+    }
+}
@@ -1,10 +1,20 @@
 pub mod simulation_settings;
 pub mod data_trait;
 pub mod simulation;
+pub mod observer;
 pub mod pronouns;
 pub mod district;
 pub mod tribute;
 pub mod event;
+pub mod event_pack;
+pub mod script;
+pub mod status_board;
+pub mod summary;
+pub mod timeline;
+pub mod export;
+pub mod import;
+pub mod brantsteele;
+pub mod cast_generator;
 
 #[cfg(test)]
 mod tests {
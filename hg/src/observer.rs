@@ -0,0 +1,218 @@
+use std::{rc::Rc, cell::RefCell, fmt};
+
+use crate::{simulation::{Simulation, PhaseLog}, event::{EventResult, EventCategory, TextSpan}, tribute::Tribute};
+
+// Gets told about everything that happens in a game, so front-ends, loggers
+// and stats collectors don't have to poll the simulation and work it out
+// themselves. Every callback does nothing by default, so only the ones
+// that matter need implementing.
+//
+// `phase_started` comes before the phase runs. Everything else is only
+// told once the phase is over and can't be rolled back, so if a phase
+// fails, there's no `phase_ended` and it's started again on the next step
+pub trait SimulationObserver {
+    fn phase_started(&mut self, _sim: &Simulation, _title: &str, _category: &EventCategory) {}
+
+    // in the order they're shown in
+    fn event_resolved(&mut self, _sim: &Simulation, _phase: &PhaseLog, _event: &EventResult) {}
+
+    // right after the event that killed them
+    fn tribute_killed(&mut self, _sim: &Simulation, _tribute: &Tribute, _killers: &[&Tribute]) {}
+
+    // `allies` are everyone else in the alliance
+    fn alliance_formed(&mut self, _sim: &Simulation, _tribute: &Tribute, _allies: &[&Tribute]) {}
+
+    // for everyone who lived through an event with an item in it
+    fn item_found(&mut self, _sim: &Simulation, _tribute: &Tribute, _item: &str) {}
+
+    fn phase_ended(&mut self, _sim: &Simulation, _phase: &PhaseLog) {}
+
+    fn game_ended(&mut self, _sim: &Simulation, _winners: &[&Tribute]) {}
+}
+
+pub type SharedObserver = Rc<RefCell<dyn SimulationObserver>>;
+
+// Clones of a simulation share its observers
+#[derive(Clone, Default)]
+pub struct Observers(Vec<SharedObserver>);
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Observers({})", self.0.len())
+    }
+}
+
+impl Observers {
+    pub fn add(&mut self, observer: SharedObserver) {
+        self.0.push(observer);
+    }
+
+    pub fn remove(&mut self, observer: &SharedObserver) {
+        self.0.retain(|other| !Rc::ptr_eq(other, observer));
+    }
+
+    pub fn notify_phase_start(&self, sim: &Simulation, category: &EventCategory) {
+        let title = sim.get_category_title();
+
+        for observer in &self.0 {
+            observer.borrow_mut().phase_started(sim, &title, category);
+        }
+    }
+
+    // Tells everyone about a phase that just finished
+    pub fn notify_phase(&self, sim: &Simulation, phase: &PhaseLog) {
+        for observer in &self.0 {
+            let mut observer = observer.borrow_mut();
+
+            for event in &phase.events {
+                observer.event_resolved(sim, phase, event);
+
+                for id in &event.killed {
+                    let tribute = match sim.get_tribute(id) {
+                        Some(tribute) => tribute,
+                        None => continue
+                    };
+
+                    let killers = event.killers
+                        .iter()
+                        .filter_map(|id| sim.get_tribute(id))
+                        .collect::<Vec<&Tribute>>();
+
+                    observer.tribute_killed(sim, tribute, &killers);
+                }
+
+                for id in &event.allies {
+                    let tribute = match sim.get_tribute(id) {
+                        Some(tribute) => tribute,
+                        None => continue
+                    };
+
+                    let allies = event.allies
+                        .iter()
+                        .filter(|ally| *ally != id)
+                        .filter_map(|id| sim.get_tribute(id))
+                        .collect::<Vec<&Tribute>>();
+
+                    observer.alliance_formed(sim, tribute, &allies);
+                }
+
+                // whoever got stabbed with the spear didn't get to keep it,
+                // the same as in the timelines
+                for span in &event.spans {
+                    let item = match span {
+                        TextSpan::Item(item) => item,
+                        _ => continue
+                    };
+
+                    for trib in &event.tributes {
+                        if event.killed.iter().any(|killed| killed == trib.get_id()) {
+                            continue;
+                        }
+
+                        if let Some(tribute) = sim.get_tribute(trib.get_id()) {
+                            observer.item_found(sim, tribute, item);
+                        }
+                    }
+                }
+            }
+
+            observer.phase_ended(sim, phase);
+        }
+    }
+
+    pub fn notify_end(&self, sim: &Simulation) {
+        let winners = sim.get_winners();
+
+        for observer in &self.0 {
+            observer.borrow_mut().game_ended(sim, &winners);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{district::District, event::Event, pronouns::Pronouns, simulation_settings::SimulationSettings};
+
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl SimulationObserver for Recorder {
+        fn phase_started(&mut self, _sim: &Simulation, title: &str, _category: &EventCategory) {
+            self.0.push(format!("start {}", title));
+        }
+
+        fn tribute_killed(&mut self, _sim: &Simulation, tribute: &Tribute, _killers: &[&Tribute]) {
+            self.0.push(format!("killed {}", tribute.get_id()));
+        }
+
+        fn phase_ended(&mut self, _sim: &Simulation, phase: &PhaseLog) {
+            self.0.push(format!("end {}", phase.title));
+        }
+
+        fn game_ended(&mut self, _sim: &Simulation, _winners: &[&Tribute]) {
+            self.0.push(String::from("game over"));
+        }
+    }
+
+    #[test]
+    fn observers_hear_about_a_whole_game() {
+        let events = [
+            "text = \"(P1) runs.\"\ncategory = \"Bloodbath\"",
+            "text = \"(P1) hides.\"\ncategory = \"Day\"",
+            "text = \"(P1) stabs (P2).\"\ncategory = \"Day\"\nkilled = [ \"(P2)\" ]\nkillers = [ \"(P1)\" ]",
+            "text = \"(P1) sleeps.\"\ncategory = \"Night\"",
+            "text = \"(P1) smothers (P2).\"\ncategory = \"Night\"\nkilled = [ \"(P2)\" ]\nkillers = [ \"(P1)\" ]"
+        ].iter().map(|event| toml::from_str(event).unwrap()).collect::<Vec<Event>>();
+
+        let tributes = [ "Katniss", "Peeta", "Rue", "Thresh", "Cato", "Clove" ]
+            .iter()
+            .map(|name| Tribute::new(name, "", Pronouns::They))
+            .collect();
+        let mut district = District::new("District 12", tributes);
+        district.file_name = String::from("district_12");
+
+        let mut settings = SimulationSettings::new();
+        settings.seed = Some(1);
+        settings.death_rate = 0.5;
+
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let mut sim = Simulation::new(vec![ district ], events, &settings);
+        sim.add_observer(recorder.clone());
+
+        for _ in 0..100 {
+            if sim.is_end() {
+                break;
+            }
+
+            sim.step().unwrap();
+        }
+
+        // stepping once the game is over shouldn't end it again
+        sim.step().unwrap();
+        assert!(sim.is_end());
+
+        let log = &recorder.borrow().0;
+
+        // every phase starts before it ends, and ends before the next one starts
+        let phases = log.iter().filter(|line| !line.starts_with("killed")).collect::<Vec<&String>>();
+        let (game_over, phases) = phases.split_last().unwrap();
+        assert_eq!(*game_over, "game over");
+        assert_eq!(phases.len(), sim.get_history().len() * 2);
+
+        for (phase, pair) in sim.get_history().iter().zip(phases.chunks(2)) {
+            assert_eq!(*pair[0], format!("start {}", phase.title));
+            assert_eq!(*pair[1], format!("end {}", phase.title));
+        }
+
+        let dead = sim.get_history()
+            .iter()
+            .flat_map(|phase| &phase.events)
+            .map(|event| event.killed.len())
+            .sum::<usize>();
+        assert!(dead > 0);
+        assert_eq!(log.iter().filter(|line| line.starts_with("killed")).count(), dead);
+        assert_eq!(dead, 6 - sim.get_winners().len());
+        assert_eq!(log.iter().filter(|line| *line == "game over").count(), 1);
+    }
+}
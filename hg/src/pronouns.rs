@@ -1,3 +1,5 @@
+use std::str::FromStr;
+
 use serde::Serialize;
 use serde::Deserialize;
 
@@ -9,6 +11,23 @@ pub enum Pronouns {
     It
 }
 
+// Accepts "he", "He/Him", "they/them" and so on
+impl FromStr for Pronouns {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let first = s.trim().split('/').next().unwrap_or("").trim().to_lowercase();
+
+        match first.as_str() {
+            "he" => Ok(Pronouns::He),
+            "she" => Ok(Pronouns::She),
+            "they" => Ok(Pronouns::They),
+            "it" => Ok(Pronouns::It),
+            _ => Err(format!("Unknown pronouns `{}`, expected one of: he, she, they, it", s.trim()))
+        }
+    }
+}
+
 fn some_kind_of_uppercase_first_letter(s: &str) -> String {
     let mut c = s.chars();
     
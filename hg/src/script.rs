@@ -0,0 +1,244 @@
+use std::{fmt, path::Path, rc::Rc};
+
+use rhai::{Engine, Scope, AST, Array, Map, Dynamic, module_resolvers::DummyModuleResolver};
+
+use crate::{tribute::Tribute, district::District, event::EventCategory, data_trait::FileError};
+
+// Events can hand the hard parts over to a Rhai script, named by the
+// event's `script` field and found next to the event file. The script
+// is run as-is whenever the event comes up, with these variables set:
+//
+// tributes   the tributes picked for (P1), (P2) and so on, in that order
+//            (so (P1) is tributes[0]). Each has `name`, `id`, `pronouns`,
+//            `kills` and `district`. Changing `kills` sticks, nothing else does
+// day, phase the day number and "Bloodbath", "Day" or "Night"
+// districts  every district, with their tributes (who also have `is_alive`)
+//
+// and these, which it can change to decide how things play out:
+//
+// eligible   set to false to turn the event down for these tributes
+// text       the event text, with (P1) and friends still to be filled in
+// killed     the player numbers that die, e.g. [2]
+// killers    the player numbers credited with the kills, e.g. [1]
+//
+// Scripts can't touch files or anything outside of these, and get
+// stopped if they run for too long
+
+// How many operations a script gets before it's stopped
+const MAX_OPERATIONS: u64 = 100_000;
+
+thread_local! {
+    static ENGINE: Engine = create_engine();
+}
+
+fn create_engine() -> Engine {
+    let mut engine = Engine::new();
+
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(32);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(10_000);
+    engine.set_max_array_size(1_000);
+    engine.set_max_map_size(1_000);
+
+    // nowhere for any of this to go
+    engine.on_print(|_| ());
+    engine.on_debug(|_, _, _| ());
+    engine.disable_symbol("eval");
+    // otherwise `import` can load any file on disk
+    engine.set_module_resolver(DummyModuleResolver::new());
+
+    engine
+}
+
+#[derive(Clone)]
+pub struct EventScript {
+    pub path: String,
+    ast: Rc<AST>
+}
+
+impl fmt::Debug for EventScript {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "EventScript({})", self.path)
+    }
+}
+
+impl PartialEq for EventScript {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path
+    }
+}
+
+// What's going on in the game, for scripts to look at
+#[derive(Debug, Clone)]
+pub struct ScriptContext {
+    pub day: u32,
+    pub phase: EventCategory,
+    pub districts: Vec<District>
+}
+
+impl Default for ScriptContext {
+    fn default() -> Self {
+        Self {
+            day: 1,
+            phase: EventCategory::Bloodbath,
+            districts: Vec::new()
+        }
+    }
+}
+
+// What a script decided
+#[derive(Debug, Clone)]
+pub struct ScriptOutcome {
+    pub eligible: bool,
+    pub text: String,
+    pub killed: Vec<u32>,
+    pub killers: Vec<u32>,
+    // the kills of each player, in the same order they were given in
+    pub kills: Vec<u32>
+}
+
+impl EventScript {
+    // Loads and compiles the script at `script`, relative
+    // to the folder of the event file that uses it
+    pub fn load(event_file: &str, script: &str) -> Result<EventScript, FileError> {
+        let path = Path::new(event_file)
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(script)
+            .to_string_lossy()
+            .to_string();
+
+        let contents = std::fs::read_to_string(&path).map_err(|source|
+            FileError::FileReadError {
+                file: path.clone(),
+                source
+            }
+        )?;
+
+        let ast = ENGINE.with(|engine| engine.compile(&contents)).map_err(|source|
+            FileError::ScriptParseError {
+                file: path.clone(),
+                source: Box::new(source)
+            }
+        )?;
+
+        Ok(EventScript {
+            path,
+            ast: Rc::new(ast)
+        })
+    }
+
+    // Runs the script for `players` (their number and tribute, P1 first),
+    // starting from the event's own text, killed and killers.
+    // Errors are returned as text
+    pub fn run(&self, text: &str, players: &[(u32, Tribute)], killed: &[u32], killers: &[u32], context: &ScriptContext) -> Result<ScriptOutcome, String> {
+        let district_of = |trib: &Tribute| context.districts
+            .iter()
+            .find(|dist| dist.tributes.iter().any(|other| other.get_id() == trib.get_id()))
+            .map(|dist| dist.name.clone())
+            .unwrap_or_default();
+
+        let mut scope = Scope::new();
+
+        scope.push("tributes", players.iter().map(|(_, trib)| Dynamic::from(tribute_map(trib, &district_of(trib)))).collect::<Array>());
+        scope.push_constant("day", context.day as i64);
+        scope.push_constant("phase", format!("{:?}", context.phase));
+        scope.push_constant("districts", context.districts.iter().map(|dist| Dynamic::from(district_map(dist))).collect::<Array>());
+
+        scope.push("eligible", true);
+        scope.push("text", text.to_string());
+        scope.push("killed", numbers_array(killed));
+        scope.push("killers", numbers_array(killers));
+
+        ENGINE.with(|engine| engine.run_ast_with_scope(&mut scope, &self.ast)).map_err(|err| err.to_string())?;
+
+        let eligible = scope.get_value::<bool>("eligible").ok_or("`eligible` should be true or false")?;
+        let text = scope.get_value::<String>("text").ok_or("`text` should be a string")?;
+        let numbers = players.iter().map(|(num, _)| *num).collect::<Vec<u32>>();
+        let killed = get_players(&scope, "killed", &numbers)?;
+        let killers = get_players(&scope, "killers", &numbers)?;
+
+        let scripted_tributes = scope.get_value::<Array>("tributes").ok_or("`tributes` should be left as a list")?;
+        let mut kills = Vec::new();
+
+        for (i, (_, trib)) in players.iter().enumerate() {
+            let trib_kills = scripted_tributes
+                .get(i)
+                .and_then(|scripted| scripted.read_lock::<Map>().and_then(|map| map.get("kills").and_then(|kills| kills.as_int().ok())))
+                .unwrap_or(trib.kills as i64);
+
+            kills.push(trib_kills.max(0) as u32);
+        }
+
+        Ok(ScriptOutcome { eligible, text, killed, killers, kills })
+    }
+}
+
+fn tribute_map(trib: &Tribute, district: &str) -> Map {
+    let mut map = Map::new();
+
+    map.insert("name".into(), trib.name.clone().into());
+    map.insert("id".into(), trib.get_id().to_string().into());
+    map.insert("pronouns".into(), format!("{:?}", trib.pronouns).into());
+    map.insert("kills".into(), (trib.kills as i64).into());
+    map.insert("is_alive".into(), trib.is_alive.into());
+    map.insert("district".into(), district.to_string().into());
+
+    map
+}
+
+fn district_map(dist: &District) -> Map {
+    let mut map = Map::new();
+
+    map.insert("name".into(), dist.name.clone().into());
+    map.insert("tributes".into(), dist.tributes.iter().map(|trib| Dynamic::from(tribute_map(trib, &dist.name))).collect::<Array>().into());
+
+    map
+}
+
+fn numbers_array(numbers: &[u32]) -> Array {
+    numbers.iter().map(|num| Dynamic::from(*num as i64)).collect()
+}
+
+// Reads a list of player numbers back out, making sure they're all in the event
+fn get_players(scope: &Scope, name: &str, players: &[u32]) -> Result<Vec<u32>, String> {
+    let array = scope.get_value::<Array>(name).ok_or(format!("`{}` should be a list of player numbers", name))?;
+    let mut numbers = Vec::new();
+
+    for value in array {
+        match value.as_int() {
+            Ok(num) if players.iter().any(|player| *player as i64 == num) => {
+                if !numbers.contains(&(num as u32)) {
+                    numbers.push(num as u32);
+                }
+            },
+            _ => return Err(format!("`{}` has {}, but the event's players are {:?}", name, value, players))
+        }
+    }
+
+    Ok(numbers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scripts_cant_import_files() {
+        let dir = std::env::temp_dir().join(format!("hg_scripts_cant_import_files_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("helper.rhai"), "fn help() { 1 }").unwrap();
+        let helper = dir.join("helper").to_string_lossy().replace('\\', "/");
+        std::fs::write(dir.join("script.rhai"), format!("import \"{}\" as helper;", helper)).unwrap();
+
+        let event_file = dir.join("event.toml").to_string_lossy().to_string();
+        // the helper is still there when the script runs
+        let result = EventScript::load(&event_file, "script.rhai")
+            .unwrap()
+            .run("(P1) waits.", &[], &[], &[], &ScriptContext::default());
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(result.is_err());
+    }
+}
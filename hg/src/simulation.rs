@@ -1,14 +1,22 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use rand::{distributions::{WeightedIndex, WeightedError}, prelude::{SliceRandom, Distribution}};
+use glob::{Pattern, PatternError};
+use hashbrown::HashMap;
+use rand::{distributions::{WeightedIndex, WeightedError}, prelude::{SliceRandom, Distribution}, rngs::StdRng, SeedableRng};
 
-use crate::{district::District, event::{EventCategory, EventResult, Event, EventError}, tribute::Tribute, simulation_settings::SimulationSettings, data_trait::{DataTrait, FileError}};
+use crate::{cast_generator::generate_cast, observer::{Observers, SharedObserver}, timeline::{Timelines, TimelineEntry}, script::ScriptContext, district::District, event_pack::EventPack, event::{EventCategory, EventResult, Event, EventError, TextSpan}, tribute::{Tribute, Death}, simulation_settings::{SimulationSettings, VictoryMode, KillCredit, random_seed}, data_trait::{DataTrait, DataFormat, FileError}};
+
+// How many events in a row can be turned down (by a script, or for killing
+// everyone who's left) before giving up on the phase instead of looping forever
+const MAX_EVENT_ATTEMPTS: u32 = 1_000;
 
 #[derive(Debug, Clone)]
 pub struct SimEvents {
     bloodbath: EventData,
     day: EventData,
     night: EventData,
+    // only their text is used, for the cannon shots
+    fallen: EventData,
 }
 
 #[derive(Debug, Clone)]
@@ -20,33 +28,118 @@ pub struct Simulation {
     killed_today: Vec<Tribute>,
     next_events: Vec<EventResult>,
     death_rate: f64,
-    prev_state: EventCategory
+    prev_state: EventCategory,
+    victory_mode: VictoryMode,
+    kill_credit: KillCredit,
+    // list how each fallen tribute died
+    show_death_causes: bool,
+    seed: u64,
+    rng: StdRng,
+    history: Vec<PhaseLog>,
+    // IDs of every fallen tribute, in the order they died
+    death_order: Vec<String>,
+    // follow-ups waiting for their phase to come around
+    pending_events: Vec<PendingEvent>,
+    timelines: Timelines,
+    // files that were skipped while loading in lenient mode
+    load_errors: Vec<String>,
+    // every event pack that was found, including the disabled ones
+    packs: Vec<EventPack>,
+    observers: Observers
+}
+
+// A follow-up event, along with the tributes it happens to
+#[derive(Debug, Clone)]
+struct PendingEvent {
+    event: Event,
+    // the tribute ID for each player number, from the event it follows
+    cast: HashMap<u32, String>,
+    // the phase it was scheduled in, so it
+    // can't happen in that same phase
+    from_phase: usize
+}
+
+// Everything that happened during a single phase of the game
+#[derive(Debug, Clone)]
+pub struct PhaseLog {
+    pub title: String,
+    pub category: EventCategory,
+    pub day: u32,
+    pub events: Vec<EventResult>
+}
+
+impl SimEvents {
+    fn create(events: &[Event]) -> Self {
+        Self {
+            bloodbath: EventData::create(
+                events.iter()
+                    .enumerate()
+                    .filter(|&(_, event)| event.category == EventCategory::Bloodbath)
+                    .map(|(_, event)| event.clone())
+                    .collect::<Vec<Event>>()
+            ),
+            day: EventData::create(
+                events.iter()
+                .enumerate()
+                .filter(|&(_, event)| event.category == EventCategory::Day)
+                .map(|(_, event)| event.clone())
+                .collect::<Vec<Event>>()
+            ),
+            night: EventData::create(
+                events.iter()
+                .enumerate()
+                .filter(|&(_, event)| event.category == EventCategory::Night)
+                .map(|(_, event)| event.clone())
+                .collect::<Vec<Event>>()
+            ),
+            fallen: EventData::create(
+                events.iter()
+                .filter(|event| event.category == EventCategory::FallenTributes)
+                .cloned()
+                .collect::<Vec<Event>>()
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
 struct EventData {
+    // the events as they were loaded
     events: Vec<Event>,
+    // what's actually picked from. Each outcome is picked like any
+    // other event, so the checks on who can die work the same for them
+    variants: Vec<Event>,
     weights: Vec<i32>,
 }
 
 impl EventData {
-    pub fn create(events_vec: Vec<Event>) -> Self {
-        let mut events = Vec::new();
+    pub fn create(events: Vec<Event>) -> Self {
+        let mut variants = Vec::new();
         let mut weights = Vec::new();
 
-        for event in events_vec.into_iter() {
-            weights.push(event.weight);
-            events.push(event);
+        for variant in events.iter().flat_map(|event| event.get_variants()) {
+            weights.push(variant.weight);
+            variants.push(variant);
         }
         
         Self {
             events,
+            variants,
             weights,
         }
     }
 
+    pub fn remove(&mut self, file_name: &str) {
+        let events = self.events
+            .drain(..)
+            .filter(|event| event.file_name != file_name)
+            .collect();
+
+        *self = EventData::create(events);
+    }
+
     pub fn has_fatal(&self) -> bool {
-        for event in &self.events {
+        for event in &self.variants {
             if event.killed.len() > 0 {
                 return true
             }
@@ -55,17 +148,26 @@ impl EventData {
         false
     }
 
-    pub fn get_random_event(&self, tributes: &Vec<Tribute>, living: usize, force_fatal: bool) -> Result<Option<Event>, SimulationError> {
+    // Any event at all, for when it doesn't matter who's left
+    pub fn get_any_event(&self, rng: &mut StdRng) -> Option<Event> {
+        let dist = WeightedIndex::new(&self.weights).ok()?;
+
+        self.variants.get(dist.sample(rng)).cloned()
+    }
+
+    pub fn get_random_event(&self, tributes: &Vec<Tribute>, living: usize, force_fatal: bool, rng: &mut StdRng) -> Result<Option<Event>, SimulationError> {
+        if self.variants.is_empty() {
+            return Err(SimulationError::NoEvents);
+        }
+
         let dist = WeightedIndex::new(&self.weights).map_err(|source|
             SimulationError::WeightedRandomError { source }
         )?;
 
-        let mut rng = rand::thread_rng();
-
         let mut loops = 0;
         
         loop {
-            match self.events.get(dist.sample(&mut rng)) {
+            match self.variants.get(dist.sample(rng)) {
                 Option::Some(ev) => {
                     if loops >= 25 {
                         if ev.get_num_tributes_required() <= tributes.len() && ev.killed.len() == 0 {
@@ -115,14 +217,38 @@ pub enum SimulationError {
         source: std::io::Error
     },
 
+    #[error("Path {path:?} is not valid UTF-8")]
+    InvalidPath {
+        path: String
+    },
+
+    #[error("Invalid file pattern {pattern:?}: {source:?}")]
+    InvalidPattern {
+        pattern: String,
+        source: PatternError
+    },
+
+    #[error("{} error(s) found while loading:\n{}", .errors.len(), get_load_errors_text(.errors))]
+    LoadErrors {
+        errors: Vec<SimulationError>
+    },
+
+    #[error("Tribute ID {id:?} in {file:?} is already used in {other_file:?}")]
+    DuplicateTributeId {
+        id: String,
+        file: String,
+        other_file: String
+    },
+
     #[error("No Districts were found")]
     NoDistricts,
     
     #[error("No Events were found")]
     NoEvents,
     
-    #[error("An event-related error occurred: {event_error:?}")]
+    #[error("An event-related error occurred in {file:?}: {event_error:?}")]
     EventError {
+        file: String,
         event_error: EventError
     },
 
@@ -135,6 +261,13 @@ pub enum SimulationError {
     MissingTributesError {
         event: String
     },
+
+    // Represents every event being turned down for the tributes that are left
+    #[error("No event could be found for {phase:?} after {attempts} attempts, check the event scripts")]
+    NoFittingEventError {
+        phase: String,
+        attempts: u32
+    },
 }
 
 impl Simulation {
@@ -144,15 +277,57 @@ impl Simulation {
         let mut a = self.events.bloodbath.events.clone();
         let mut b = self.events.day.events.clone();
         let mut c = self.events.night.events.clone();
+        let mut d = self.events.fallen.events.clone();
         
         events.append(&mut a);
         events.append(&mut b);
         events.append(&mut c);
+        events.append(&mut d);
 
         events
     }
 
+    // Advances the simulation by one phase. If anything goes wrong, the
+    // simulation is left exactly as it was before the call, so the caller
+    // can skip the offending event or reload the event files and try again
     pub fn step(&mut self) -> Result<(), SimulationError> {
+        let snapshot = self.clone();
+        let phases = self.history.len();
+        let was_end = self.is_end();
+
+        if !was_end {
+            self.observers.notify_phase_start(self, &self.state);
+        }
+
+        let result = self.step_phase();
+
+        if result.is_err() {
+            *self = snapshot;
+            return result;
+        }
+
+        // stepping at the end of the game doesn't add a phase
+        if let Some(phase) = self.history.get(phases) {
+            self.observers.notify_phase(self, phase);
+        }
+
+        if !was_end && self.is_end() {
+            self.observers.notify_end(self);
+        }
+
+        Ok(())
+    }
+
+    // The observer is told about every phase from now on, across resets
+    pub fn add_observer(&mut self, observer: SharedObserver) {
+        self.observers.add(observer);
+    }
+
+    pub fn remove_observer(&mut self, observer: &SharedObserver) {
+        self.observers.remove(observer);
+    }
+
+    fn step_phase(&mut self) -> Result<(), SimulationError> {
         let mut events = Vec::new();
 
         // if the simulations over, we just wanna skip out
@@ -169,18 +344,103 @@ impl Simulation {
         let mut living_this_step = self.get_living_tributes().len();
 
         let mut tribs_to_die = (living_this_step as f64 * self.death_rate) as i32;
-        
+
+        // follow-ups from earlier phases come before anything else
+        if self.state != EventCategory::FallenTributes {
+            let phase = self.history.len();
+            let state = self.state.clone();
+            let (due, waiting): (Vec<PendingEvent>, Vec<PendingEvent>) = std::mem::take(&mut self.pending_events)
+                .into_iter()
+                .partition(|pending| pending.event.category == state && pending.from_phase < phase);
+
+            self.pending_events = waiting;
+
+            for mut pending in due {
+                let cast = pending.cast
+                    .iter()
+                    .filter_map(|(num, id)| self.get_tribute(id).map(|trib| (*num, trib.clone())))
+                    .collect::<HashMap<u32, Tribute>>();
+                let players = pending.event.get_player_numbers()
+                    .into_iter()
+                    .filter_map(|num| cast.get(&num))
+                    .collect::<Vec<&Tribute>>();
+
+                // it's off if anyone in it has died
+                if players.iter().any(|trib| !trib.is_alive) {
+                    continue;
+                }
+
+                // someone's already been in another follow-up this
+                // phase, so it'll have to wait for the next one
+                if players.iter().any(|trib| !tributes_left.iter().any(|other| other.get_id() == trib.get_id())) {
+                    pending.from_phase = phase;
+                    self.pending_events.push(pending);
+                    continue;
+                }
+
+                // outcomes are picked by weight, like they are for other events
+                let mut event = pending.event
+                    .get_variants()
+                    .choose_weighted(&mut self.rng, |variant| variant.weight.max(0))
+                    .cloned()
+                    .unwrap_or(pending.event);
+
+                let file = event.file_name.clone();
+                let context = match event.compiled_script {
+                    Some(_) => self.get_script_context(),
+                    None => ScriptContext::default()
+                };
+
+                let result = event.get_result_for(&cast, &context).map_err(|event_error|
+                    SimulationError::EventError { file, event_error }
+                )?;
+
+                let mut event_result = match result {
+                    Some(result) if result.killed.len() < living_this_step => result,
+                    _ => continue
+                };
+
+                tribs_to_die -= event_result.killed.len() as i32;
+                living_this_step -= event_result.killed.len();
+
+                self.apply_result(&mut event_result);
+                self.schedule_follow_ups(&event, &event_result);
+
+                for trib in &event_result.tributes {
+                    tributes_left.retain(|t| t.get_id() != trib.get_id());
+                }
+
+                events.push(event_result);
+            }
+        }
+
+        let mut attempts = 0;
+
         while tributes_left.len() != 0 {
+            if attempts >= MAX_EVENT_ATTEMPTS {
+                return Err(SimulationError::NoFittingEventError {
+                    phase: self.get_category_title(),
+                    attempts
+                });
+            }
+
             // "Fallen Tributes" is a unique event that just
             // lists out tributes that died within the last 24 hours (in-game)
             if self.state == EventCategory::FallenTributes {
-                let gunshots = if self.killed_today.len() < 1 {
-                    String::from("No cannon shots can be heard in the distance.\n")
-                } else {
-                    if self.killed_today.len() == 1 {
-                        format!("1 cannon shot can be heard in the distance.\n")
+                // the FallenTributes event files say what the cannons sound like.
+                // The text is picked with its own rng so loading those files
+                // doesn't change how a seeded game plays out
+                let mut cannon_rng = StdRng::seed_from_u64(self.seed.wrapping_add(self.day as u64));
+                let gunshots = match self.events.fallen.get_any_event(&mut cannon_rng) {
+                    Some(event) => format!("{}\n", event.get_fallen_text(self.killed_today.len())),
+                    None => if self.killed_today.len() < 1 {
+                        String::from("No cannon shots can be heard in the distance.\n")
                     } else {
-                        format!("{} cannon shots can be heard in the distance.\n", self.killed_today.len())
+                        if self.killed_today.len() == 1 {
+                            format!("1 cannon shot can be heard in the distance.\n")
+                        } else {
+                            format!("{} cannon shots can be heard in the distance.\n", self.killed_today.len())
+                        }
                     }
                 };
 
@@ -189,7 +449,34 @@ impl Simulation {
                 // Create death events for each dead tribute,
                 // listing their name and district
                 for trib in &self.killed_today {
-                    let mut event = EventResult::new(&format!("{} from {}", trib.name, self.get_trib_dist_name(trib)));
+                    let mut spans = vec![
+                        TextSpan::Tribute { id: trib.get_id().to_string(), name: trib.name.clone() },
+                        TextSpan::Text(format!(" from {}", self.get_trib_dist_name(trib)))
+                    ];
+
+                    if let Some(death) = trib.death.as_ref().filter(|_| self.show_death_causes) {
+                        spans.push(TextSpan::Text(format!(": {}", death.cause)));
+
+                        let killers = death.killers
+                            .iter()
+                            .filter_map(|id| self.get_tribute(id))
+                            .collect::<Vec<&Tribute>>();
+
+                        for (i, killer) in killers.iter().enumerate() {
+                            spans.push(TextSpan::Text(String::from(match i {
+                                0 => " (killed by ",
+                                _ if i + 1 == killers.len() => " and ",
+                                _ => ", "
+                            })));
+                            spans.push(TextSpan::Tribute { id: killer.get_id().to_string(), name: killer.name.clone() });
+                        }
+
+                        if !killers.is_empty() {
+                            spans.push(TextSpan::Text(String::from(")")));
+                        }
+                    }
+
+                    let mut event = EventResult::from_spans(spans);
                     
                     event.tributes.push(trib.clone());
 
@@ -207,38 +494,42 @@ impl Simulation {
             let event = self.get_rand_event(&tributes_left, living_this_step,  force_fatal)?;
             
 
-            let event_result = if event.is_none() {
+            let (ev, mut event_result) = if event.is_none() {
+                attempts += 1;
                 continue;
             } else {
                 let mut ev = event.unwrap();
+                let file = ev.file_name.clone();
 
-                tribs_to_die -= ev.killed.len() as i32;
-                living_this_step -= ev.killed.len();
-
-                ev.get_result(&mut tributes_left).map_err(|event_error|
-                    SimulationError::EventError { event_error }
-                )?
-            };
-            
-            
-            for dist in &mut self.districts {
-                for trib in &mut dist.tributes {
-                    // kill tributes that need killin
-                    for trib_id in &event_result.killed {
-                        if trib.get_id() == *trib_id {
-                            trib.kill();
-                            self.killed_today.push(trib.clone());
-                        }
-                    }
-
-                    // reward tributes that need rewardin
-                    for trib_id in &event_result.killers {
-                        if trib.get_id() == *trib_id {
-                            trib.add_kill();
-                        }
+                // scripts get to look at the game as it is right now
+                let context = match ev.compiled_script {
+                    Some(_) => self.get_script_context(),
+                    None => ScriptContext::default()
+                };
+                let tributes_before = tributes_left.clone();
+
+                let result = ev.get_result(&mut tributes_left, &mut self.rng, &context).map_err(|event_error|
+                    SimulationError::EventError { file, event_error }
+                )?;
+
+                match result {
+                    // scripts pick who dies, but someone has to be left standing
+                    Some(result) if result.killed.len() < living_this_step => (ev, result),
+                    _ => {
+                        tributes_left = tributes_before;
+                        attempts += 1;
+                        continue;
                     }
                 }
-            }
+            };
+
+            attempts = 0;
+
+            tribs_to_die -= event_result.killed.len() as i32;
+            living_this_step -= event_result.killed.len();
+
+            self.apply_result(&mut event_result);
+            self.schedule_follow_ups(&ev, &event_result);
             
             for trib in &event_result.tributes {
                 tributes_left.retain(|t| t.get_id() != trib.get_id());
@@ -249,10 +540,19 @@ impl Simulation {
         }
 
         if self.state != EventCategory::FallenTributes {
-            let mut rng = rand::thread_rng();
-            events.shuffle(&mut rng);
+            events.shuffle(&mut self.rng);
         }
         
+        let phase = PhaseLog {
+            title: self.get_category_title(),
+            category: self.state.clone(),
+            day: self.day,
+            events: events.clone()
+        };
+
+        self.timelines.record(self.history.len(), &phase);
+        self.history.push(phase);
+
         self.next_events = events;
         
         self.prev_state = self.state.clone();
@@ -267,6 +567,83 @@ impl Simulation {
         Ok(())
     }
 
+    // Kills and rewards the tributes in an event. Killers that
+    // don't get the kill are moved over to the assists
+    fn apply_result(&mut self, event_result: &mut EventResult) {
+        if self.kill_credit == KillCredit::FirstKiller && event_result.killers.len() > 1 {
+            let mut assists = event_result.killers.split_off(1);
+            assists.append(&mut event_result.assists);
+            event_result.assists = assists;
+        }
+
+        for dist in &mut self.districts {
+            for trib in &mut dist.tributes {
+                // scripts can change how many kills a tribute has
+                if let Some(scripted) = event_result.tributes.iter().find(|other| other.get_id() == trib.get_id()) {
+                    trib.kills = scripted.kills;
+                }
+
+                // kill tributes that need killin
+                for trib_id in &event_result.killed {
+                    if trib.get_id() == *trib_id {
+                        trib.kill();
+                        trib.death = Some(Death {
+                            day: self.day,
+                            phase: self.state.clone(),
+                            killers: event_result.killers.clone(),
+                            assists: event_result.assists.clone(),
+                            cause: event_result.cause.clone()
+                        });
+                        self.killed_today.push(trib.clone());
+                        self.death_order.push(trib.get_id().to_string());
+                    }
+                }
+
+                // reward tributes that need rewardin, once for everyone that died
+                for _ in &event_result.killed {
+                    if event_result.killers.iter().any(|id| trib.get_id() == *id) {
+                        trib.add_kill();
+                    }
+
+                    if event_result.assists.iter().any(|id| trib.get_id() == *id) {
+                        trib.add_assist();
+                    }
+                }
+            }
+        }
+    }
+
+    fn schedule_follow_ups(&mut self, event: &Event, event_result: &EventResult) {
+        // the tributes come out in player number order
+        let cast = event.get_player_numbers()
+            .into_iter()
+            .zip(event_result.tributes.iter().map(|trib| trib.get_id().to_string()))
+            .collect::<HashMap<u32, String>>();
+
+        for follow_up in &event.follow_ups {
+            let mut follow_up = follow_up.clone();
+
+            // so errors point at the file it came from
+            if follow_up.file_name.is_empty() {
+                follow_up.file_name = event.file_name.clone();
+            }
+
+            self.pending_events.push(PendingEvent {
+                event: follow_up,
+                cast: cast.clone(),
+                from_phase: self.history.len()
+            });
+        }
+    }
+
+    fn get_script_context(&self) -> ScriptContext {
+        ScriptContext {
+            day: self.day,
+            phase: self.state.clone(),
+            districts: self.districts.clone()
+        }
+    }
+
     pub fn get_category_title(&self) -> String {
         match self.state {
             EventCategory::Bloodbath => String::from("The Bloodbath"),
@@ -291,41 +668,149 @@ impl Simulation {
     pub fn has_next_events(&self) -> bool {
         self.next_events.len() > 0
     }
+
+    pub fn get_history(&self) -> &Vec<PhaseLog> {
+        &self.history
+    }
+
+    pub fn get_death_order(&self) -> &Vec<String> {
+        &self.death_order
+    }
+
+    pub fn get_day(&self) -> u32 {
+        self.day
+    }
+
+    // Resolves every event once against the loaded tributes without
+    // touching the game, and returns everything that went wrong
+    pub fn validate_events(&self) -> Vec<SimulationError> {
+        let tributes = self.districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter().cloned())
+            .collect::<Vec<Tribute>>();
+
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let mut errors = Vec::new();
+
+        // follow-ups can only use the players of the event they follow,
+        // and only come up in the Bloodbath, Day and Night
+        for event in self.get_all_events() {
+            if let Err(event_error) = event.check_follow_ups() {
+                errors.push(SimulationError::EventError {
+                    file: event.file_name.clone(),
+                    event_error
+                });
+            }
+        }
+
+        // follow-ups are checked with random tributes, just like everything else
+        let events = self.get_all_events()
+            .iter()
+            .flat_map(|event| event.with_follow_ups())
+            .collect::<Vec<Event>>();
+
+        for event in events {
+            let required = event.get_num_tributes_required();
+
+            // every outcome is tried, but an event only gets one error
+            let result = if required > tributes.len() {
+                Err(EventError::NotEnoughTributes {
+                    event: event.text.clone(),
+                    required,
+                    available: tributes.len()
+                })
+            } else {
+                event.get_variants()
+                    .iter_mut()
+                    .try_for_each(|variant| variant.get_result(&mut tributes.clone(), &mut rng, &self.get_script_context()).map(|_| ()))
+            };
+
+            if let Err(event_error) = result {
+                errors.push(SimulationError::EventError {
+                    file: event.file_name.clone(),
+                    event_error
+                });
+            }
+        }
+
+        errors
+    }
+
+    // Starts the game over with the same tributes and events.
+    // Passing `None` picks a new random seed
+    pub fn reset(&mut self, seed: Option<u64>) {
+        for dist in &mut self.districts {
+            for trib in &mut dist.tributes {
+                trib.is_alive = true;
+                trib.kills = 0;
+                trib.assists = 0;
+                trib.death = None;
+            }
+        }
+
+        self.seed = seed.unwrap_or_else(random_seed);
+        self.rng = StdRng::seed_from_u64(self.seed);
+        self.state = EventCategory::Bloodbath;
+        self.prev_state = EventCategory::Bloodbath;
+        self.day = 1;
+        self.killed_today.clear();
+        self.next_events.clear();
+        self.history.clear();
+        self.death_order.clear();
+        self.pending_events.clear();
+        self.timelines.clear();
+    }
+
+    // Removes an event from the pool for the rest of the game
+    pub fn skip_event(&mut self, file_name: &str) {
+        self.events.bloodbath.remove(file_name);
+        self.events.day.remove(file_name);
+        self.events.night.remove(file_name);
+        self.events.fallen.remove(file_name);
+
+        // follow-ups carry the file name of the event they came from
+        self.pending_events.retain(|pending| pending.event.file_name != file_name);
+    }
+
+    // Swaps out the event pool without touching the tributes or the
+    // game log, so broken event files can be fixed mid-game. Follow-ups
+    // that are still waiting are dropped, they're from the old events
+    pub fn reload_events(&mut self, events: Vec<Event>) {
+        self.events = SimEvents::create(&events);
+        self.pending_events.clear();
+    }
 }
 
 impl Simulation {
-    pub fn new(districts: Vec<District>, events: Vec<Event>, death_rate: f64) -> Simulation {
+    pub fn new(mut districts: Vec<District>, events: Vec<Event>, settings: &SimulationSettings) -> Simulation {
+        let seed = settings.seed.unwrap_or_else(random_seed);
+
+        // for districts that didn't come from `collect_districts`
+        for dist in &mut districts {
+            dist.fill_tribute_ids();
+        }
+
         Simulation {
             districts,
-            events: SimEvents {
-                bloodbath: EventData::create(
-                    events.iter()
-                        .enumerate()
-                        .filter(|&(_, event)| event.category == EventCategory::Bloodbath)
-                        .map(|(_, event)| event.clone())
-                        .collect::<Vec<Event>>()
-                ),
-                day: EventData::create(
-                    events.iter()
-                    .enumerate()
-                    .filter(|&(_, event)| event.category == EventCategory::Day)
-                    .map(|(_, event)| event.clone())
-                    .collect::<Vec<Event>>()
-                ),
-                night: EventData::create(
-                    events.iter()
-                    .enumerate()
-                    .filter(|&(_, event)| event.category == EventCategory::Night)
-                    .map(|(_, event)| event.clone())
-                    .collect::<Vec<Event>>()
-                ),
-            },
+            events: SimEvents::create(&events),
             state: EventCategory::Bloodbath,
             day: 1,
             killed_today: Vec::new(),
             next_events: Vec::new(),
-            death_rate,
-            prev_state: EventCategory::Bloodbath
+            death_rate: settings.death_rate,
+            prev_state: EventCategory::Bloodbath,
+            victory_mode: settings.victory_mode,
+            kill_credit: settings.kill_credit,
+            show_death_causes: settings.show_death_causes,
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            history: Vec::new(),
+            death_order: Vec::new(),
+            pending_events: Vec::new(),
+            timelines: Timelines::default(),
+            load_errors: Vec::new(),
+            packs: Vec::new(),
+            observers: Observers::default()
         }
     }
 
@@ -372,19 +857,38 @@ impl Simulation {
     }
 
     pub fn has_winner(&self) -> bool {
-        let mut living = 0;
-        
-        for dist in &self.districts {
-            if dist.has_living_tributes() {
-                for trib in &dist.tributes {
-                    if trib.is_alive {
-                        living += 1;
-                    }
-                }
-            }
+        match self.victory_mode {
+            VictoryMode::LastTribute => self.get_living_tributes().len() <= 1,
+            VictoryMode::LastDistrict => self.districts
+                .iter()
+                .filter(|&dist| dist.has_living_tributes())
+                .count() <= 1
         }
+    }
 
-        living <= 1
+    // every tribute still standing at the end. In `LastTribute` mode
+    // this is just the winner, in `LastDistrict` mode it's the
+    // surviving members of the winning district
+    pub fn get_winners(&self) -> Vec<&Tribute> {
+        self.districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter())
+            .filter(|trib| trib.is_alive)
+            .collect()
+    }
+
+    pub fn get_victory_text(&self) -> Option<String> {
+        let winners = self.get_winners();
+        let first = winners.first()?;
+        let dist_name = self.get_trib_dist_name(first);
+
+        if winners.len() == 1 {
+            Some(format!("The winner is {} from {}!", first.name, dist_name))
+        } else {
+            let names = winners.iter().map(|trib| trib.name.clone()).collect::<Vec<String>>();
+
+            Some(format!("The winners are {} from {}!", names.join(", "), dist_name))
+        }
     }
 
     pub fn get_winner(&self) -> Option<&Tribute> {
@@ -404,6 +908,26 @@ impl Simulation {
         Option::None
     }
 
+    pub fn get_tribute(&self, id: &str) -> Option<&Tribute> {
+        self.districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter())
+            .find(|trib| trib.get_id() == id)
+    }
+
+    // case-insensitive, since it's mostly used for user input
+    pub fn get_tribute_by_name(&self, name: &str) -> Option<&Tribute> {
+        self.districts
+            .iter()
+            .flat_map(|dist| dist.tributes.iter())
+            .find(|trib| trib.name.to_lowercase() == name.trim().to_lowercase())
+    }
+
+    // Everything a tribute took part in, and what it meant for them
+    pub fn get_timeline(&self, id: &str) -> &[TimelineEntry] {
+        self.timelines.get(id)
+    }
+
     pub fn get_trib_dist_name(&self, tribute: &Tribute) -> String {
         for dist in &self.districts {
             for trib in &dist.tributes {
@@ -443,11 +967,11 @@ impl Simulation {
         living
     }
     
-    pub fn get_rand_event(&self, tributes: &Vec<Tribute>, living: usize, force_fatal: bool) -> Result<Option<Event>, SimulationError> {
+    pub fn get_rand_event(&mut self, tributes: &Vec<Tribute>, living: usize, force_fatal: bool) -> Result<Option<Event>, SimulationError> {
         match self.state {
-            EventCategory::Bloodbath => self.events.bloodbath.get_random_event(tributes, living, force_fatal),
-            EventCategory::Day => self.events.day.get_random_event(tributes, living, force_fatal),
-            EventCategory::Night => self.events.night.get_random_event(tributes, living, force_fatal),
+            EventCategory::Bloodbath => self.events.bloodbath.get_random_event(tributes, living, force_fatal, &mut self.rng),
+            EventCategory::Day => self.events.day.get_random_event(tributes, living, force_fatal, &mut self.rng),
+            EventCategory::Night => self.events.night.get_random_event(tributes, living, force_fatal, &mut self.rng),
             _ => return Ok(None)
         }
     }
@@ -464,35 +988,176 @@ impl Simulation {
     pub fn get_districts(&self) -> Vec<District> {
         self.districts.clone()
     }
+
+    pub fn get_seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn get_victory_mode(&self) -> VictoryMode {
+        self.victory_mode
+    }
+
+    pub fn get_show_death_causes(&self) -> bool {
+        self.show_death_causes
+    }
+
+    pub fn get_load_errors(&self) -> &Vec<String> {
+        &self.load_errors
+    }
+
+    pub fn get_packs(&self) -> &Vec<EventPack> {
+        &self.packs
+    }
 }
 
 pub fn build_sim(settings: SimulationSettings) -> Result<Simulation, SimulationError> {
-    let mut events = Vec::new();
-    let mut districts = Vec::new();
+    let (packs, mut errors) = collect_event_packs(&settings)?;
+    let events = get_enabled_events(&packs, &settings);
+
+    // a made up cast comes from the game's seed, so replaying the seed brings it back
+    let seed = settings.seed.unwrap_or_else(random_seed);
+    let (districts, mut district_errors) = match &settings.random_cast {
+        Some(cast) => (generate_cast(cast, seed)?, Vec::new()),
+        None => collect_districts(&settings)?
+    };
+    errors.append(&mut district_errors);
+
+    if !errors.is_empty() && !settings.lenient {
+        return Err(SimulationError::LoadErrors { errors });
+    }
 
-    for path in settings.event_folders {
-        match load_data_from_dir::<Event>(&path) {
-            Ok(mut evs) => events.append(&mut evs),
-            Err(e) => return Err(e)
-        };
+    if events.is_empty() {
+        return Err(SimulationError::NoEvents);
     }
 
-    for path in settings.district_folders {
-        match load_data_from_dir::<District>(&path) {
-            Ok(mut dists) => districts.append(&mut dists),
-            Err(e) => return Err(e)
-        };
+    if districts.is_empty() {
+        return Err(SimulationError::NoDistricts);
     }
 
-    if events.len() < 1 {
+    let mut sim = Simulation::new(districts, events, &SimulationSettings { seed: Some(seed), ..settings });
+    sim.load_errors = errors.iter().map(get_load_error_text).collect();
+    sim.packs = packs.into_iter().filter(|pack| !pack.is_single).collect();
+
+    Ok(sim)
+}
+
+pub fn load_events(settings: &SimulationSettings) -> Result<Vec<Event>, SimulationError> {
+    let (events, errors) = collect_events(settings)?;
+
+    if !errors.is_empty() && !settings.lenient {
+        return Err(SimulationError::LoadErrors { errors });
+    }
+
+    if events.is_empty() {
         return Err(SimulationError::NoEvents);
     }
 
-    if districts.len() < 1 {
-        return Err(SimulationError::NoDistricts);
+    Ok(events)
+}
+
+// Loads every event file it can, along with the errors
+// for the ones it couldn't, instead of stopping at the first
+pub fn collect_events(settings: &SimulationSettings) -> Result<(Vec<Event>, Vec<SimulationError>), SimulationError> {
+    let (packs, errors) = collect_event_packs(settings)?;
+
+    Ok((get_enabled_events(&packs, settings), errors))
+}
+
+// Every event file is loaded as a pack, single events are packs of one
+pub fn collect_event_packs(settings: &SimulationSettings) -> Result<(Vec<EventPack>, Vec<SimulationError>), SimulationError> {
+    let patterns = compile_patterns(&settings.event_patterns)?;
+
+    let mut packs = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in &settings.event_folders {
+        packs.append(&mut load_data_from_dir::<EventPack>(path, &patterns, &mut errors));
+    }
+
+    Ok((packs, errors))
+}
+
+fn get_enabled_events(packs: &[EventPack], settings: &SimulationSettings) -> Vec<Event> {
+    packs
+        .iter()
+        .filter(|pack| pack.is_single || !settings.disabled_packs.iter().any(|name| name == pack.get_name()))
+        .flat_map(|pack| pack.events.iter().cloned())
+        .collect()
+}
+
+pub fn collect_districts(settings: &SimulationSettings) -> Result<(Vec<District>, Vec<SimulationError>), SimulationError> {
+    let patterns = compile_patterns(&settings.district_patterns)?;
+
+    let mut districts = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in &settings.district_folders {
+        districts.append(&mut load_data_from_dir::<District>(path, &patterns, &mut errors));
     }
 
-    Ok(Simulation::new(districts, events, settings.death_rate))
+    check_tribute_ids(&mut districts, &mut errors);
+
+    Ok((districts, errors))
+}
+
+// Makes up IDs for the tributes that don't have one, then leaves out
+// any district that reuses an ID, since they have to be unique
+fn check_tribute_ids(districts: &mut Vec<District>, errors: &mut Vec<SimulationError>) {
+    // ID -> the file it's from
+    let mut seen: HashMap<String, String> = HashMap::new();
+
+    districts.retain_mut(|dist| {
+        dist.fill_tribute_ids();
+
+        let mut ids: HashMap<String, String> = HashMap::new();
+        let mut duplicates = Vec::new();
+
+        for trib in &dist.tributes {
+            let id = trib.get_id().to_string();
+
+            match seen.get(&id).or_else(|| ids.get(&id)) {
+                Some(other_file) => duplicates.push(SimulationError::DuplicateTributeId {
+                    id,
+                    file: dist.file_name.clone(),
+                    other_file: other_file.clone()
+                }),
+                None => {
+                    ids.insert(id, dist.file_name.clone());
+                }
+            }
+        }
+
+        if !duplicates.is_empty() {
+            errors.append(&mut duplicates);
+            return false;
+        }
+
+        seen.extend(ids);
+        true
+    });
+}
+
+// One line per error, e.g. "events/event_day_nap.toml:3:8: expected an equals, found a newline"
+pub fn get_load_error_text(err: &SimulationError) -> String {
+    match err {
+        SimulationError::FileError { file, source } => {
+            // problems with an event's script are about the script, not the event file
+            let file = match source {
+                FileError::ScriptParseError { file, .. } | FileError::FileReadError { file, .. } => file,
+                _ => file
+            };
+
+            match source.get_position() {
+                Some((line, column)) => format!("{}:{}:{}: {}", file, line, column, source.get_message()),
+                None => format!("{}: {}", file, source.get_message())
+            }
+        },
+        err => err.to_string()
+    }
+}
+
+fn get_load_errors_text(errors: &[SimulationError]) -> String {
+    errors.iter().map(get_load_error_text).collect::<Vec<String>>().join("\n")
 }
 
 /*pub async fn build_sim_async(settings: SimulationSettings) -> std::result::Result<Simulation, String> {
@@ -509,32 +1174,265 @@ pub fn build_sim(settings: SimulationSettings) -> Result<Simulation, SimulationE
     }
 }*/
 
-fn load_data_from_dir<T: DataTrait<Output = T>>(dir: &str) -> Result<Vec<T>, SimulationError> {
+// Loads every data file in `dir` and its subfolders. If any `patterns`
+// are given, only the files matching at least one of them are loaded
+fn load_data_from_dir<T: DataTrait<Output = T>>(dir: &str, patterns: &[Pattern], errors: &mut Vec<SimulationError>) -> Vec<T> {
     let mut files = Vec::new();
+    let mut paths = Vec::new();
 
-    let dir_contents = std::fs::read_dir(dir).map_err(|source|
-        SimulationError::DirectoryReadError {
-            dir: dir.to_string(),
-            source
-        }
-    )?;
+    // still load whatever was found before things went wrong
+    if let Err(err) = find_data_files(Path::new(dir), Path::new(dir), patterns, &mut paths) {
+        errors.push(err);
+    }
+
+    // sort the paths so the same seed always produces the same game,
+    // no matter what order the OS hands us the files in
+    paths.sort();
+
+    for path in paths {
+        let file_path = match path.to_str() {
+            Some(file_path) => file_path,
+            None => {
+                errors.push(SimulationError::InvalidPath {
+                    path: path.to_string_lossy().to_string()
+                });
+                continue;
+            }
+        };
 
-    for entry in dir_contents {
+        match T::from_file(file_path) {
+            Ok(mut file) => {
+                let relative_path = path.strip_prefix(dir).unwrap_or(&path);
+                file.set_path(&relative_path.to_string_lossy());
 
-        let f = entry.unwrap();
-        let path = f.path();
-        let file_path = path.to_str().unwrap();
-        let mut file = T::from_file(file_path).map_err(|source|
-            SimulationError::FileError {
+                files.push(file);
+            },
+            Err(source) => errors.push(SimulationError::FileError {
                 file: file_path.to_string(),
                 source
+            })
+        }
+    }
+
+    files
+}
+
+// Collects the paths of all data files in `dir`, going into subfolders.
+// Hidden files and folders (starting with a '.') are skipped, and so are
+// symlinked folders, so there's no chance of going around in circles
+fn find_data_files(root: &Path, dir: &Path, patterns: &[Pattern], paths: &mut Vec<PathBuf>) -> Result<(), SimulationError> {
+    let dir_read_error = |source: std::io::Error| SimulationError::DirectoryReadError {
+        dir: dir.to_string_lossy().to_string(),
+        source
+    };
+
+    for entry in std::fs::read_dir(dir).map_err(dir_read_error)? {
+        let entry = entry.map_err(dir_read_error)?;
+        let path = entry.path();
+
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+
+        if entry.file_type().map_err(dir_read_error)?.is_dir() {
+            find_data_files(root, &path, patterns, paths)?;
+        } else if DataFormat::from_path(&path).is_some() && matches_patterns(root, &path, patterns) {
+            paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+// Patterns are checked against both the file name and the
+// path inside the data folder, e.g. "event_day_*.toml" or "bloodbath/*"
+fn matches_patterns(root: &Path, path: &Path, patterns: &[Pattern]) -> bool {
+    if patterns.is_empty() {
+        return true;
+    }
+
+    let relative_path = path.strip_prefix(root).unwrap_or(path);
+    let file_name = Path::new(path.file_name().unwrap_or_default());
+
+    patterns.iter().any(|pattern|
+        pattern.matches_path(relative_path) || pattern.matches_path(file_name)
+    )
+}
+
+fn compile_patterns(patterns: &[String]) -> Result<Vec<Pattern>, SimulationError> {
+    patterns
+        .iter()
+        .map(|pattern| Pattern::new(pattern).map_err(|source|
+            SimulationError::InvalidPattern {
+                pattern: pattern.clone(),
+                source
             }
-        )?;
+        ))
+        .collect()
+}
 
-        file.set_path(Path::new(&file_path).file_name().unwrap().to_str().unwrap());
-        
-        files.push(file);
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pronouns::Pronouns;
+
+    #[test]
+    fn districts_in_subfolders_get_their_own_ids() {
+        let dir = std::env::temp_dir().join(format!("hg_districts_in_subfolders_{}", std::process::id()));
+        let district = "name = \"District\"\n\n[[tributes]]\nname = \"Amy\"\navatar_url = \"\"\npronouns = \"She\"\n";
+
+        for folder in [ "arena", "capitol" ] {
+            std::fs::create_dir_all(dir.join(folder)).unwrap();
+            std::fs::write(dir.join(folder).join("district_1.toml"), district).unwrap();
+        }
+
+        let mut settings = SimulationSettings::new();
+        settings.district_folders = vec![ dir.to_string_lossy().to_string() ];
+
+        let (districts, errors) = collect_districts(&settings).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        let ids = districts
+            .iter()
+            .map(|dist| dist.tributes[0].get_id())
+            .collect::<Vec<&str>>();
+
+        assert!(errors.is_empty());
+        assert_eq!(ids, vec![ "arena/district_1:1", "capitol/district_1:1" ]);
+        assert_eq!(districts[0].file_name, "arena/district_1");
+    }
+
+    #[test]
+    fn events_keep_their_outcomes() {
+        let event: Event = toml::from_str(r#"
+            text = "(P1) finds a river."
+            category = "Day"
+
+            [[outcomes]]
+            text = "(P1) drinks from it."
+
+            [[outcomes]]
+            text = "(P1) drowns."
+            killed = [ "(P1)" ]
+        "#).unwrap();
+
+        let sim = Simulation::new(Vec::new(), vec![ event.clone() ], &SimulationSettings::new());
+
+        assert_eq!(sim.get_all_events(), vec![ event ]);
+        assert!(sim.events.day.has_fatal());
+    }
+
+    fn events(toml: &[&str]) -> Vec<Event> {
+        toml.iter().map(|event| toml::from_str(event).unwrap()).collect()
+    }
+
+    fn district(names: &[&str]) -> District {
+        let tributes = names
+            .iter()
+            .map(|name| Tribute::new(name, "", Pronouns::They))
+            .collect();
+
+        let mut dist = District::new("District 12", tributes);
+        dist.file_name = String::from("district_12");
+
+        dist
     }
 
-    Ok(files)
-}
\ No newline at end of file
+    #[test]
+    fn follow_ups_wait_for_the_next_phase() {
+        let events = events(&[
+            "text = \"(P1) runs.\"\ncategory = \"Bloodbath\"",
+            "text = \"(P1) sleeps.\"\ncategory = \"Night\"",
+            r#"
+                text = "(P1) sets a trap."
+                category = "Day"
+
+                [[follow_ups]]
+                text = "(P1) checks the trap."
+                category = "Day"
+            "#
+        ]);
+
+        let mut settings = SimulationSettings::new();
+        settings.seed = Some(1);
+        settings.death_rate = 0.0;
+
+        let mut sim = Simulation::new(vec![ district(&[ "Katniss", "Peeta", "Rue", "Thresh" ]) ], events, &settings);
+
+        for _ in 0..5 {
+            sim.step().unwrap();
+        }
+
+        let texts = |phase: usize| sim.get_history()[phase].events
+            .iter()
+            .map(|event| event.text.clone())
+            .collect::<Vec<String>>();
+
+        assert_eq!(sim.get_history()[1].title, "Day 1");
+        assert!(texts(1).iter().all(|text| text.ends_with("sets a trap.")));
+        assert_eq!(sim.get_history()[4].title, "Day 2");
+        assert_eq!(texts(4).iter().filter(|text| text.ends_with("checks the trap.")).count(), 4);
+    }
+
+    fn kill_credit_sim(kill_credit: KillCredit) -> Simulation {
+        let mut settings = SimulationSettings::new();
+        settings.seed = Some(1);
+        settings.kill_credit = kill_credit;
+
+        Simulation::new(vec![ district(&[ "Katniss", "Peeta", "Cato" ]) ], Vec::new(), &settings)
+    }
+
+    fn ganging_up() -> EventResult {
+        let mut result = EventResult::new("Katniss and Peeta gang up on Cato.");
+        result.killed = vec![ String::from("district_12:3") ];
+        result.killers = vec![ String::from("district_12:1"), String::from("district_12:2") ];
+        result.cause = String::from("ganged up on");
+
+        result
+    }
+
+    #[test]
+    fn everyone_gets_the_kill_by_default() {
+        let mut sim = kill_credit_sim(KillCredit::Everyone);
+        sim.apply_result(&mut ganging_up());
+
+        assert_eq!(sim.get_tribute("district_12:1").unwrap().kills, 1);
+        assert_eq!(sim.get_tribute("district_12:2").unwrap().kills, 1);
+        assert_eq!(sim.get_tribute("district_12:2").unwrap().assists, 0);
+
+        let cato = sim.get_tribute("district_12:3").unwrap();
+        assert!(!cato.is_alive);
+        assert_eq!(cato.death.as_ref().unwrap().cause, "ganged up on");
+    }
+
+    #[test]
+    fn first_killer_gets_the_kill_and_the_rest_assist() {
+        let mut sim = kill_credit_sim(KillCredit::FirstKiller);
+        let mut result = ganging_up();
+        sim.apply_result(&mut result);
+
+        assert_eq!(result.killers, vec![ "district_12:1" ]);
+        assert_eq!(result.assists, vec![ "district_12:2" ]);
+        assert_eq!(sim.get_tribute("district_12:1").unwrap().kills, 1);
+        assert_eq!(sim.get_tribute("district_12:2").unwrap().kills, 0);
+        assert_eq!(sim.get_tribute("district_12:2").unwrap().assists, 1);
+        assert_eq!(sim.get_tribute("district_12:3").unwrap().death.as_ref().unwrap().killers, vec![ "district_12:1" ]);
+    }
+
+    #[test]
+    fn fallen_tributes_list_how_they_died() {
+        let mut settings = SimulationSettings::new();
+        settings.seed = Some(1);
+        settings.show_death_causes = true;
+
+        let mut sim = Simulation::new(vec![ district(&[ "Katniss", "Peeta", "Cato" ]) ], Vec::new(), &settings);
+        sim.apply_result(&mut ganging_up());
+        sim.state = EventCategory::FallenTributes;
+        sim.step().unwrap();
+
+        let phase = sim.get_history().last().unwrap();
+        assert_eq!(phase.category, EventCategory::FallenTributes);
+        assert_eq!(phase.events[0].text, "1 cannon shot can be heard in the distance.\n");
+        assert_eq!(phase.events[1].text, "Cato from District 12: ganged up on (killed by Katniss and Peeta)");
+    }
+}
@@ -1,12 +1,68 @@
 use serde::{Serialize, Deserialize};
 
-use crate::data_trait::FileError;
+use crate::{cast_generator::CastSettings, data_trait::{DataFormat, FileError, write_data_file}};
 
-#[derive(Serialize, Deserialize, Clone)]
+pub const SETTINGS_FILE: &str = "simulation.toml";
+
+// TOML only has signed 64-bit integers, so seeds are kept
+// at or below this to make sure they can be saved
+pub const MAX_SEED: u64 = i64::MAX as u64;
+
+pub fn random_seed() -> u64 {
+    rand::random::<u64>() & MAX_SEED
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum VictoryMode {
+    // The game ends when a single tribute is left alive
+    #[default]
+    LastTribute,
+    // The game ends when all living tributes are from the same district
+    LastDistrict
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy, Serialize, Deserialize)]
+pub enum KillCredit {
+    // Everyone listed as a killer gets the kill
+    #[default]
+    Everyone,
+    // Only the first killer (by player number) gets the kill,
+    // the rest get an assist
+    FirstKiller
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct SimulationSettings {
     pub event_folders: Vec<String>,
     pub district_folders: Vec<String>,
-    pub death_rate: f64
+    // only files matching one of these are loaded. If there
+    // are none, every data file in the folders is loaded
+    #[serde(default)]
+    pub event_patterns: Vec<String>,
+    #[serde(default)]
+    pub district_patterns: Vec<String>,
+    // names of the event packs that shouldn't be loaded
+    #[serde(default)]
+    pub disabled_packs: Vec<String>,
+    pub death_rate: f64,
+    // if no seed is given, a random one is picked for every game
+    #[serde(default)]
+    pub seed: Option<u64>,
+    #[serde(default)]
+    pub victory_mode: VictoryMode,
+    // who gets the kill when an event has more than one killer
+    #[serde(default)]
+    pub kill_credit: KillCredit,
+    // list how each of the fallen tributes died
+    #[serde(default)]
+    pub show_death_causes: bool,
+    // skip files that can't be loaded instead of refusing to start
+    #[serde(default)]
+    pub lenient: bool,
+    // makes up a cast instead of loading the district folders.
+    // Kept last, since TOML tables have to come after the plain values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub random_cast: Option<CastSettings>
 }
 
 impl SimulationSettings {
@@ -14,10 +70,21 @@ impl SimulationSettings {
         Self {
             event_folders: vec![ String::from("events/") ],
             district_folders: vec![ String::from("districts/") ],
-            death_rate: 0.17
+            event_patterns: Vec::new(),
+            district_patterns: Vec::new(),
+            disabled_packs: Vec::new(),
+            death_rate: 0.17,
+            seed: None,
+            victory_mode: VictoryMode::LastTribute,
+            kill_credit: KillCredit::Everyone,
+            show_death_causes: false,
+            lenient: false,
+            random_cast: None
         }
     }
 
+    // The settings can be in any of the data formats, going by
+    // the extension. Anything unknown is assumed to be TOML
     pub fn parse(path: &str) -> Result<Self, FileError> {
         // Attempt to read the data in a file
         let contents = std::fs::read_to_string(path).map_err(|source|
@@ -27,42 +94,56 @@ impl SimulationSettings {
             }
         )?;
 
-        // Attempt to parse the data to TOML
-        let toml = toml::from_str(&contents).map_err(|source|
-            FileError::TOMLParseError {
-                file: path.to_string(),
-                source
-            }
-        )?;
-
-        Ok(toml)
+        DataFormat::from_path(path).unwrap_or_default().parse(path, &contents)
     }
 
+    // Saves in the same format `parse` would read the file as
     pub fn save(&self, path: &str) -> Result<Self, FileError> {
-        let contents = toml::to_string(self).map_err(|source|
-            FileError::TOMLSerializeError {
-                file: String::from(path),
-                source
-            }
-        )?;
-
-        std::fs::write(path, contents).map_err(|source|
-            FileError::FileWriteError {
-                file: String::from(path),
-                source
-            }
-        )?;
+        write_data_file(path, self, DataFormat::from_path(path).unwrap_or_default())?;
 
         Ok(self.clone())
     }
 }
 
 pub fn fetch_or_create() -> Result<SimulationSettings, FileError> {
-    let path = "simulation.toml";
+    let path = SETTINGS_FILE;
 
     if std::path::Path::exists(std::path::Path::new(path)) {
         SimulationSettings::parse(path)
     } else {
         SimulationSettings::new().save(path)
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_round_trip() {
+        // the process ID keeps test runs from stepping on each other
+        let path = std::env::temp_dir()
+            .join(format!("hg_settings_round_trip_{}.toml", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        let mut settings = SimulationSettings::new();
+        settings.seed = Some(MAX_SEED);
+        settings.victory_mode = VictoryMode::LastDistrict;
+        settings.kill_credit = KillCredit::FirstKiller;
+        settings.random_cast = Some(CastSettings::new(3, 2));
+
+        settings.save(&path).unwrap();
+        let parsed = SimulationSettings::parse(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(parsed, settings);
+    }
+
+    #[test]
+    fn random_seeds_can_be_saved() {
+        for _ in 0..100 {
+            assert!(random_seed() <= MAX_SEED);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use crate::{simulation::Simulation, summary::get_kills_text};
+
+// how many districts are shown side by side in the CLI status board
+const CLI_COLUMNS: usize = 4;
+
+#[derive(Debug, Clone)]
+pub struct TributeStatus {
+    pub id: String,
+    pub name: String,
+    pub is_alive: bool,
+    pub kills: u32,
+    pub assists: u32,
+    pub death_day: Option<u32>,
+    pub death_cause: Option<String>,
+    pub killers: Vec<String>
+}
+
+#[derive(Debug, Clone)]
+pub struct DistrictStatus {
+    pub name: String,
+    pub tributes: Vec<TributeStatus>
+}
+
+impl TributeStatus {
+    pub fn get_kills_text(&self) -> String {
+        get_kills_text(self.kills, self.assists)
+    }
+
+    // e.g. "Alive" or "Deceased (Day 2)"
+    pub fn get_state_text(&self) -> String {
+        match (self.is_alive, self.death_day) {
+            (true, _) => String::from("Alive"),
+            (false, Some(day)) => format!("Deceased (Day {})", day),
+            (false, None) => String::from("Deceased")
+        }
+    }
+
+    pub fn get_killers_text(&self) -> Option<String> {
+        if self.killers.is_empty() {
+            None
+        } else {
+            Some(format!("Killed by {}", self.killers.join(", ")))
+        }
+    }
+}
+
+impl Simulation {
+    pub fn get_status_board(&self) -> Vec<DistrictStatus> {
+        let mut board = Vec::new();
+
+        for dist in self.get_districts() {
+            let mut tributes = Vec::new();
+
+            for trib in &dist.tributes {
+                let (death_day, death_cause, killers) = match &trib.death {
+                    Some(death) => (
+                        Some(death.day),
+                        Some(death.cause.clone()),
+                        death.killers
+                            .iter()
+                            .filter_map(|id| self.get_tribute(id))
+                            .map(|killer| killer.name.clone())
+                            .collect()
+                    ),
+                    None => (None, None, Vec::new())
+                };
+
+                tributes.push(TributeStatus {
+                    id: trib.get_id().to_string(),
+                    name: trib.name.clone(),
+                    is_alive: trib.is_alive,
+                    kills: trib.kills,
+                    assists: trib.assists,
+                    death_day,
+                    death_cause,
+                    killers
+                });
+            }
+
+            board.push(DistrictStatus {
+                name: dist.name.clone(),
+                tributes
+            });
+        }
+
+        board
+    }
+
+    // Lays the status board out as a grid of districts, a few per row
+    pub fn cli_display_status(&self) -> String {
+        let cells = self.get_status_board()
+            .iter()
+            .map(|dist| {
+                let mut lines = vec![ dist.name.clone(), "-".repeat(dist.name.len()) ];
+
+                for trib in &dist.tributes {
+                    lines.push(trib.name.clone());
+                    lines.push(format!("  {} [{}]", trib.get_state_text(), trib.get_kills_text()));
+                    // always take up the line, so every row of tributes lines up
+                    lines.push(format!("  {}", trib.get_killers_text().unwrap_or_default()));
+                }
+
+                lines
+            })
+            .collect::<Vec<Vec<String>>>();
+
+        let width = cells
+            .iter()
+            .flatten()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0) + 4;
+
+        let mut board = String::from("Status board:\n");
+
+        for row in cells.chunks(CLI_COLUMNS) {
+            let height = row.iter().map(|cell| cell.len()).max().unwrap_or(0);
+
+            for i in 0..height {
+                let line = row
+                    .iter()
+                    .map(|cell| format!("{:<width$}", cell.get(i).map(String::as_str).unwrap_or(""), width = width))
+                    .collect::<String>();
+
+                board.push_str(line.trim_end());
+                board.push('\n');
+            }
+
+            board.push('\n');
+        }
+
+        board
+    }
+}
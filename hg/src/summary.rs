@@ -0,0 +1,218 @@
+use serde::Serialize;
+
+use crate::{simulation::Simulation, event::EventCategory, tribute::Tribute};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Placement {
+    // 1 is the winner. In `LastDistrict` mode, every survivor shares 1st place
+    pub place: usize,
+    pub name: String,
+    pub district: String,
+    pub kills: u32,
+    pub assists: u32
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DistrictResult {
+    pub name: String,
+    pub best_place: usize,
+    pub kills: u32,
+    pub survivors: usize
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct KillRecord {
+    // empty if they died on their own
+    pub killers: Vec<String>,
+    pub assists: Vec<String>,
+    pub victim: String,
+    pub cause: String,
+    pub day: u32,
+    pub phase: EventCategory
+}
+
+// Everything worth knowing once the games are over
+#[derive(Debug, Clone, Serialize)]
+pub struct GameSummary {
+    pub days: u32,
+    pub placements: Vec<Placement>,
+    // placements with at least 1 kill, most kills first
+    pub kill_leaderboard: Vec<Placement>,
+    pub districts: Vec<DistrictResult>,
+    pub kills: Vec<KillRecord>,
+    // deaths nobody gets the kill for
+    pub deaths_without_killer: usize
+}
+
+impl Simulation {
+    pub fn get_summary(&self) -> GameSummary {
+        let mut placements = Vec::new();
+
+        let winners = self.get_winners();
+
+        for winner in &winners {
+            placements.push(self.placement(1, winner));
+        }
+
+        // the last tribute to fall comes right after the winners
+        for (i, id) in self.get_death_order().iter().rev().enumerate() {
+            if let Some(trib) = self.get_tribute(id) {
+                placements.push(self.placement(winners.len().max(1) + i + 1, trib));
+            }
+        }
+
+        let mut kill_leaderboard = placements
+            .iter()
+            .filter(|placement| placement.kills > 0)
+            .cloned()
+            .collect::<Vec<Placement>>();
+        kill_leaderboard.sort_by(|a, b| b.kills.cmp(&a.kills).then(a.place.cmp(&b.place)));
+
+        let mut districts = self.get_districts()
+            .iter()
+            .map(|dist| DistrictResult {
+                name: dist.name.clone(),
+                best_place: placements
+                    .iter()
+                    .filter(|placement| placement.district == dist.name)
+                    .map(|placement| placement.place)
+                    .min()
+                    .unwrap_or(0),
+                kills: dist.tributes.iter().map(|trib| trib.kills).sum(),
+                survivors: dist.get_living().len()
+            })
+            .collect::<Vec<DistrictResult>>();
+        districts.sort_by_key(|dist| dist.best_place);
+
+        let mut kills = Vec::new();
+
+        for id in self.get_death_order() {
+            let victim = match self.get_tribute(id) {
+                Some(trib) => trib,
+                None => continue
+            };
+
+            if let Some(death) = &victim.death {
+                let names = |ids: &[String]| ids
+                    .iter()
+                    .filter_map(|id| self.get_tribute(id))
+                    .map(|trib| trib.name.clone())
+                    .collect();
+
+                kills.push(KillRecord {
+                    killers: names(&death.killers),
+                    assists: names(&death.assists),
+                    victim: victim.name.clone(),
+                    cause: death.cause.clone(),
+                    day: death.day,
+                    phase: death.phase.clone()
+                });
+            }
+        }
+
+        let deaths_without_killer = kills.iter().filter(|kill| kill.killers.is_empty()).count();
+
+        GameSummary {
+            days: self.get_day(),
+            placements,
+            kill_leaderboard,
+            districts,
+            kills,
+            deaths_without_killer
+        }
+    }
+
+    fn placement(&self, place: usize, trib: &Tribute) -> Placement {
+        Placement {
+            place,
+            name: trib.name.clone(),
+            district: self.get_trib_dist_name(trib),
+            kills: trib.kills,
+            assists: trib.assists
+        }
+    }
+}
+
+impl Placement {
+    pub fn get_kills_text(&self) -> String {
+        get_kills_text(self.kills, self.assists)
+    }
+}
+
+// e.g. "2 kills", or "1 kill, 3 assists". Assists are only mentioned if there are any
+pub fn get_kills_text(kills: u32, assists: u32) -> String {
+    let kill_txt = if kills == 1 { "kill" } else { "kills" };
+
+    match assists {
+        0 => format!("{} {}", kills, kill_txt),
+        1 => format!("{} {}, 1 assist", kills, kill_txt),
+        _ => format!("{} {}, {} assists", kills, kill_txt, assists)
+    }
+}
+
+impl KillRecord {
+    // e.g. "Day 2: Katrina was killed by Tina"
+    pub fn get_text(&self) -> String {
+        let when = match self.phase {
+            EventCategory::Bloodbath => String::from("Bloodbath"),
+            EventCategory::Night => format!("Night {}", self.day),
+            _ => format!("Day {}", self.day)
+        };
+
+        let assists = if self.assists.is_empty() {
+            String::new()
+        } else {
+            format!(", with help from {}", self.assists.join(", "))
+        };
+
+        if self.killers.is_empty() {
+            format!("{}: {} died{}", when, self.victim, assists)
+        } else {
+            format!("{}: {} was killed by {}{}", when, self.victim, self.killers.join(", "), assists)
+        }
+    }
+}
+
+impl GameSummary {
+    pub fn get_days_text(&self) -> String {
+        if self.days == 1 {
+            String::from("The games lasted 1 day.")
+        } else {
+            format!("The games lasted {} days.", self.days)
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("===== Summary\n");
+        text.push_str(&format!("{}\n", self.get_days_text()));
+
+        text.push_str("\nFinal placements:\n");
+        for placement in &self.placements {
+            text.push_str(&format!("\t{}. {} ({}) [{}]\n", placement.place, placement.name, placement.district, placement.get_kills_text()));
+        }
+
+        text.push_str("\nKill leaderboard:\n");
+        if self.kill_leaderboard.is_empty() {
+            text.push_str("\tNobody killed anyone!\n");
+        }
+        for placement in &self.kill_leaderboard {
+            text.push_str(&format!("\t{} ({}): {}\n", placement.name, placement.district, placement.kills));
+        }
+
+        text.push_str("\nDistricts:\n");
+        for dist in &self.districts {
+            text.push_str(&format!("\t{}: best placement {}, {} kills, {} survivors\n", dist.name, dist.best_place, dist.kills, dist.survivors));
+        }
+
+        text.push_str("\nKills:\n");
+        for kill in &self.kills {
+            text.push_str(&format!("\t{}\n", kill.get_text()));
+        }
+
+        if self.deaths_without_killer > 0 {
+            text.push_str(&format!("\t({} without a killer)\n", self.deaths_without_killer));
+        }
+
+        text
+    }
+}
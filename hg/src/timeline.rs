@@ -0,0 +1,188 @@
+use hashbrown::HashMap;
+use serde::Serialize;
+
+use crate::{simulation::{Simulation, PhaseLog}, event::{EventCategory, TextSpan}};
+
+// What an event meant for one of the tributes in it
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub enum TimelineFact {
+    // the IDs of who they killed
+    Killed { victims: Vec<String> },
+    // the IDs of who they helped kill
+    Assisted { victims: Vec<String> },
+    Injured,
+    // an item that came up in an event they lived through, e.g. (item:spear)
+    Item(String),
+    // the IDs of who they teamed up with
+    Allied { allies: Vec<String> },
+    // `killers` are IDs, `cause` is the same as the tribute's death cause
+    Died { killers: Vec<String>, cause: String }
+}
+
+impl TimelineFact {
+    pub fn get_text(&self, sim: &Simulation) -> String {
+        let names = |ids: &[String]| ids
+            .iter()
+            .map(|id| sim.get_tribute(id).map(|trib| trib.name.clone()).unwrap_or_else(|| id.clone()))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        // the cause is usually the event text, so it's only
+        // added when the game is set to list them
+        let with_cause = |text: String, cause: &str| match sim.get_show_death_causes() && !cause.is_empty() {
+            true => format!("{}: {}", text, cause),
+            false => text
+        };
+
+        match self {
+            TimelineFact::Killed { victims } => format!("Killed {}", names(victims)),
+            TimelineFact::Assisted { victims } => format!("Helped kill {}", names(victims)),
+            TimelineFact::Injured => String::from("Was injured"),
+            TimelineFact::Item(item) => format!("Item: {}", item),
+            TimelineFact::Allied { allies } => format!("Teamed up with {}", names(allies)),
+            TimelineFact::Died { killers, cause } if killers.is_empty() => with_cause(String::from("Died"), cause),
+            TimelineFact::Died { killers, cause } => with_cause(format!("Killed by {}", names(killers)), cause)
+        }
+    }
+}
+
+// One event in a tribute's timeline
+#[derive(Debug, Clone, Serialize)]
+pub struct TimelineEntry {
+    // where the event is in the game's history, so front-ends
+    // can show it the same way they show every other event
+    pub phase: usize,
+    pub event: usize,
+    pub day: u32,
+    pub title: String,
+    pub text: String,
+    pub facts: Vec<TimelineFact>
+}
+
+// Everything each tribute took part in, by ID, kept up as the game goes
+#[derive(Debug, Clone, Default)]
+pub struct Timelines(HashMap<String, Vec<TimelineEntry>>);
+
+impl Timelines {
+    // Adds the events of a phase, which is at `index` in the game's history
+    pub fn record(&mut self, index: usize, phase: &PhaseLog) {
+        // the fallen tributes are listed there, but nothing happens to them
+        if phase.category == EventCategory::FallenTributes {
+            return;
+        }
+
+        for (i, event) in phase.events.iter().enumerate() {
+            let items = event.spans
+                .iter()
+                .filter_map(|span| match span {
+                    TextSpan::Item(item) => Some(item.clone()),
+                    _ => None
+                })
+                .collect::<Vec<String>>();
+
+            for trib in &event.tributes {
+                let id = trib.get_id();
+                let mut facts = Vec::new();
+
+                if event.killers.iter().any(|killer| killer == id) {
+                    let victims = event.killed
+                        .iter()
+                        .filter(|victim| *victim != id)
+                        .cloned()
+                        .collect::<Vec<String>>();
+
+                    if !victims.is_empty() {
+                        facts.push(TimelineFact::Killed { victims });
+                    }
+                }
+
+                if event.assists.iter().any(|assist| assist == id) {
+                    facts.push(TimelineFact::Assisted { victims: event.killed.clone() });
+                }
+
+                if event.injured.iter().any(|injured| injured == id) {
+                    facts.push(TimelineFact::Injured);
+                }
+
+                // whoever got stabbed with the spear didn't get to keep it
+                if !event.killed.iter().any(|killed| killed == id) {
+                    for item in &items {
+                        facts.push(TimelineFact::Item(item.clone()));
+                    }
+                }
+
+                if event.allies.iter().any(|ally| ally == id) {
+                    let allies = event.allies
+                        .iter()
+                        .filter(|ally| *ally != id)
+                        .cloned()
+                        .collect::<Vec<String>>();
+
+                    facts.push(TimelineFact::Allied { allies });
+                }
+
+                if event.killed.iter().any(|killed| killed == id) {
+                    facts.push(TimelineFact::Died { killers: event.killers.clone(), cause: event.cause.clone() });
+                }
+
+                self.0.entry(id.to_string()).or_default().push(TimelineEntry {
+                    phase: index,
+                    event: i,
+                    day: phase.day,
+                    title: phase.title.clone(),
+                    text: event.text.trim_end().to_string(),
+                    facts
+                });
+            }
+        }
+    }
+
+    pub fn get(&self, id: &str) -> &[TimelineEntry] {
+        self.0.get(id).map(|entries| entries.as_slice()).unwrap_or_default()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{event::EventResult, pronouns::Pronouns, tribute::Tribute};
+
+    #[test]
+    fn kills_show_up_for_the_killer_and_the_victim() {
+        let mut katniss = Tribute::new("Katniss", "", Pronouns::She);
+        katniss.set_id("district_12:1");
+        let mut cato = Tribute::new("Cato", "", Pronouns::He);
+        cato.set_id("district_2:1");
+
+        let mut event = EventResult::new("Katniss shoots Cato. ");
+        event.tributes = vec![ katniss, cato ];
+        event.killed = vec![ String::from("district_2:1") ];
+        event.killers = vec![ String::from("district_12:1") ];
+        event.cause = String::from("an arrow");
+
+        let mut timelines = Timelines::default();
+        timelines.record(1, &PhaseLog {
+            title: String::from("Day 1"),
+            category: EventCategory::Day,
+            day: 1,
+            events: vec![ event ]
+        });
+
+        let killer = timelines.get("district_12:1");
+        assert_eq!(killer.len(), 1);
+        assert_eq!(killer[0].phase, 1);
+        assert_eq!(killer[0].text, "Katniss shoots Cato.");
+        assert_eq!(killer[0].facts, vec![ TimelineFact::Killed { victims: vec![ String::from("district_2:1") ] } ]);
+
+        let victim = timelines.get("district_2:1");
+        assert_eq!(victim.len(), 1);
+        assert_eq!(victim[0].facts, vec![ TimelineFact::Died {
+            killers: vec![ String::from("district_12:1") ],
+            cause: String::from("an arrow")
+        } ]);
+    }
+}
@@ -1,22 +1,25 @@
-use std::sync::atomic::{AtomicUsize, Ordering};
-
 use serde::{Serialize, Deserialize};
 
-use crate::pronouns::Pronouns;
-
-// generate a new sequential player ID. Normally
-// this would be randomized for better security, but it
-// doesn't really matter in this case
-fn new_id() -> usize {
-    static COUNTER: AtomicUsize = AtomicUsize::new(1);
-
-    COUNTER.fetch_add(1, Ordering::Relaxed)
-}
+use crate::{pronouns::Pronouns, event::EventCategory};
 
 fn default_true() -> bool {
     true
 }
 
+// When and how a tribute died
+#[derive(Debug, Clone, PartialEq)]
+pub struct Death {
+    pub day: u32,
+    pub phase: EventCategory,
+    // IDs of the tributes credited with the kill. Nobody
+    // is, if they died on their own (e.g. to the weather)
+    pub killers: Vec<String>,
+    // IDs of the tributes that helped
+    pub assists: Vec<String>,
+    // the event's cause of death, or its text if it doesn't have one
+    pub cause: String
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Tribute {
     pub name: String,
@@ -25,19 +28,41 @@ pub struct Tribute {
     #[serde(skip)]
     pub kills: u32,
     #[serde(skip)]
+    pub assists: u32,
+    #[serde(skip)]
     #[serde(default = "default_true")]
     pub is_alive: bool,
+    #[serde(skip)]
+    pub death: Option<Death>,
     //#[serde(skip)]
     //#[serde(default = "def_cache_img")]
     //pub avatar: CachedImage,
-    #[serde(skip)]
-    #[serde(default = "new_id")]
-    id: usize
+    // can be given in the district file, otherwise it's made up from the
+    // file and the tribute's place in it when loaded, e.g. "district_1:2"
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    id: String
 }
 
 impl Tribute {
-    pub fn get_id(&self) -> usize {
-        self.id
+    pub fn new(name: &str, avatar_url: &str, pronouns: Pronouns) -> Self {
+        Self {
+            name: String::from(name),
+            avatar_url: String::from(avatar_url),
+            pronouns,
+            kills: 0,
+            assists: 0,
+            is_alive: true,
+            death: None,
+            id: String::new()
+        }
+    }
+
+    pub fn get_id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn set_id(&mut self, id: &str) {
+        self.id = String::from(id);
     }
 
     pub fn kill(&mut self) {
@@ -48,6 +73,10 @@ impl Tribute {
         self.kills += 1;
     }
 
+    pub fn add_assist(&mut self) {
+        self.assists += 1;
+    }
+
     /*pub async fn update_avatar(&self) -> Result<Tribute, Error> {
         let mut tribute = self.clone();
         tribute.avatar = CachedImage::new(self.avatar_url.clone());
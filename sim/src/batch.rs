@@ -0,0 +1,110 @@
+use std::{collections::HashMap, rc::Rc, cell::RefCell};
+
+use serde::Serialize;
+use hg::{simulation::{Simulation, SimulationError}, observer::SimulationObserver, tribute::Tribute, export::{ExportFormat, escape_html}};
+
+use crate::cli::run_to_end;
+
+#[derive(Serialize)]
+pub struct BatchStats {
+    pub games: u32,
+    pub average_days: f64,
+    // (name, wins), most wins first
+    pub tribute_wins: Vec<(String, u32)>,
+    pub district_wins: Vec<(String, u32)>
+}
+
+// Counts up the winners of every game it sees
+#[derive(Default)]
+struct WinTally {
+    total_days: u64,
+    tribute_wins: HashMap<String, u32>,
+    district_wins: HashMap<String, u32>
+}
+
+impl SimulationObserver for WinTally {
+    fn game_ended(&mut self, sim: &Simulation, winners: &[&Tribute]) {
+        self.total_days += sim.get_day() as u64;
+
+        for winner in winners {
+            let name = format!("{} ({})", winner.name, sim.get_trib_dist_name(winner));
+            *self.tribute_wins.entry(name).or_insert(0) += 1;
+        }
+
+        if let Some(winner) = winners.first() {
+            *self.district_wins.entry(sim.get_trib_dist_name(winner)).or_insert(0) += 1;
+        }
+    }
+}
+
+pub fn run_batch(mut simulation: Simulation, games: u32, seed: Option<u64>) -> Result<BatchStats, SimulationError> {
+    let tally = Rc::new(RefCell::new(WinTally::default()));
+    simulation.add_observer(tally.clone());
+
+    for game in 0..games {
+        // with a seed, every batch is reproducible
+        simulation.reset(seed.map(|seed| seed.wrapping_add(game as u64)));
+        run_to_end(&mut simulation)?;
+    }
+
+    let tally = tally.take();
+
+    Ok(BatchStats {
+        games,
+        average_days: if games > 0 { tally.total_days as f64 / games as f64 } else { 0.0 },
+        tribute_wins: sorted_wins(tally.tribute_wins),
+        district_wins: sorted_wins(tally.district_wins)
+    })
+}
+
+fn sorted_wins(wins: HashMap<String, u32>) -> Vec<(String, u32)> {
+    let mut wins = wins.into_iter().collect::<Vec<(String, u32)>>();
+    wins.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+
+    wins
+}
+
+impl BatchStats {
+    pub fn to_string(&self, format: ExportFormat) -> String {
+        match format {
+            ExportFormat::Json => serde_json::to_string_pretty(self).unwrap_or_default(),
+            ExportFormat::Html => self.to_html(),
+            ExportFormat::Text => self.to_text()
+        }
+    }
+
+    fn to_html(&self) -> String {
+        let mut html = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Hunger Games Simulator</title>\n</head>\n<body>\n");
+        html.push_str("<h1>Hunger Games Simulator</h1>\n");
+        html.push_str(&format!("<p>{} games, {:.1} days on average</p>\n", self.games, self.average_days));
+
+        for (title, wins) in [ ("District Wins", &self.district_wins), ("Tribute Wins", &self.tribute_wins) ] {
+            html.push_str(&format!("<h3>{}</h3>\n<table>\n", title));
+            for (name, wins) in wins {
+                html.push_str(&format!("<tr><td>{}</td><td>{}</td><td>{:.1}%</td></tr>\n",
+                    escape_html(name), wins, *wins as f64 / self.games as f64 * 100.0));
+            }
+            html.push_str("</table>\n");
+        }
+
+        html.push_str("</body>\n</html>\n");
+
+        html
+    }
+
+    fn to_text(&self) -> String {
+        let mut text = format!("{} games, {:.1} days on average\n", self.games, self.average_days);
+
+        text.push_str("\nDistrict wins:\n");
+        for (name, wins) in &self.district_wins {
+            text.push_str(&format!("\t{}: {} ({:.1}%)\n", name, wins, *wins as f64 / self.games as f64 * 100.0));
+        }
+
+        text.push_str("\nTribute wins:\n");
+        for (name, wins) in &self.tribute_wins {
+            text.push_str(&format!("\t{}: {} ({:.1}%)\n", name, wins, *wins as f64 / self.games as f64 * 100.0));
+        }
+
+        text
+    }
+}
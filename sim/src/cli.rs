@@ -0,0 +1,58 @@
+use hg::{simulation::{Simulation, SimulationError}, event::EventCategory};
+
+use crate::{repl::{prompt, ReplAction}, style::CliStyle};
+
+pub fn run_cli(mut simulation: Simulation, auto: bool, style: CliStyle) -> Result<(), SimulationError> {
+    let mut auto = auto;
+    // phases left to run before we ask the user again
+    let mut remaining = 0;
+
+    loop {
+        println!("{}", style.title(&simulation.get_category_title()));
+        let is_end = simulation.is_end();
+
+        simulation.step()?;
+
+        if is_end {
+            if let Some(victory) = simulation.get_victory_text() {
+                println!("{}", style.victory(&victory));
+            } else {
+                println!("There were no winners this round! (??? Shouldn't happen!! Report if it does)");
+            }
+
+            println!("\n{}", simulation.get_summary().to_text());
+            return Ok(())
+        } else {
+            let category = simulation.get_history()
+                .last()
+                .map(|phase| phase.category.clone())
+                .unwrap_or(EventCategory::Day);
+
+            for event in &simulation.get_clear_next_events() {
+                println!("{}", style.event(event, &category))
+            }
+        }
+
+        if remaining > 1 {
+            remaining -= 1;
+        } else if !auto {
+            match prompt(&simulation) {
+                ReplAction::Continue(phases) => remaining = phases,
+                ReplAction::Auto => auto = true,
+                ReplAction::Quit => return Ok(())
+            }
+        }
+
+        println!();
+    }
+}
+
+// Steps through the rest of the game without printing anything
+pub fn run_to_end(simulation: &mut Simulation) -> Result<(), SimulationError> {
+    while !simulation.is_end() {
+        simulation.step()?;
+        simulation.get_clear_next_events();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use hg::{brantsteele::{import_events, import_cast}, import::{import_cast_csv, save_districts, CastImport, ImportProblem}, data_trait::FileError};
+
+// Converts the given files, writing the events to `<dir>/events/` and the
+// districts to `<dir>/districts/`. Lines that couldn't be converted are listed
+pub fn import_brantsteele(event_list: Option<&str>, cast: Option<&str>, dir: &str) -> Result<(), FileError> {
+    if let Some(file) = event_list {
+        let import = import_events(&read_file(file)?, "brantsteele");
+        let events_dir = Path::new(dir).join("events").to_string_lossy().to_string();
+
+        std::fs::create_dir_all(&events_dir).map_err(|source|
+            FileError::FileWriteError {
+                file: events_dir.clone(),
+                source
+            }
+        )?;
+
+        let path = import.pack.save(&events_dir)?;
+        println!("Imported {} events into {}", import.pack.events.len(), path);
+
+        print_problems(file, &import.problems);
+    }
+
+    if let Some(file) = cast {
+        save_cast(file, import_cast(&read_file(file)?), dir)?;
+    }
+
+    Ok(())
+}
+
+// Reads a cast from a CSV file, writing the districts to `<dir>/districts/`
+pub fn import_csv(file: &str, dir: &str) -> Result<(), FileError> {
+    save_cast(file, import_cast_csv(&read_file(file)?), dir)
+}
+
+fn save_cast(file: &str, import: CastImport, dir: &str) -> Result<(), FileError> {
+    let districts_dir = Path::new(dir).join("districts").to_string_lossy().to_string();
+
+    save_districts(&import.districts, &districts_dir)?;
+
+    let tributes = import.districts.iter().map(|dist| dist.tributes.len()).sum::<usize>();
+    println!("Imported {} tributes in {} districts into {}", tributes, import.districts.len(), districts_dir);
+
+    print_problems(file, &import.problems);
+
+    Ok(())
+}
+
+fn read_file(file: &str) -> Result<String, FileError> {
+    std::fs::read_to_string(file).map_err(|source|
+        FileError::FileReadError {
+            file: file.to_string(),
+            source
+        }
+    )
+}
+
+fn print_problems(file: &str, problems: &[ImportProblem]) {
+    if problems.is_empty() {
+        return;
+    }
+
+    eprintln!("{} line(s) in {} could not be converted:", problems.len(), file);
+
+    for problem in problems {
+        eprintln!("  {}", problem);
+    }
+}
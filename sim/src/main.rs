@@ -1,59 +1,315 @@
-use hg::{simulation::{build_sim, Simulation}, simulation_settings::fetch_or_create};
+use clap::{Parser, Subcommand};
+use hg::{cast_generator::CastSettings, simulation::build_sim, simulation_settings::{fetch_or_create, SimulationSettings, SETTINGS_FILE, MAX_SEED}, export::{ExportFormat, export}};
+
+mod cli;
+mod repl;
+mod style;
+mod batch;
+mod import;
+
+// Exit codes. Invalid arguments exit with 2, mostly courtesy of clap
+const EXIT_SIMULATION_ERROR: i32 = 1;
+const EXIT_ARGS_ERROR: i32 = 2;
+const EXIT_SETTINGS_ERROR: i32 = 3;
+const EXIT_LOAD_ERROR: i32 = 4;
+const EXIT_WRITE_ERROR: i32 = 5;
+const EXIT_TERMINAL_ERROR: i32 = 6;
+
+/// Hunger Games simulator inspired by BrantSteele's HG Sim
+#[derive(Parser)]
+#[clap(name = "hg_sim", version, after_help = "EXIT CODES:\n    0    Success\n    1    The simulation failed while running\n    2    Invalid arguments\n    3    The settings file could not be loaded\n    4    The event or district files could not be loaded, or are invalid\n    5    The output could not be written\n    6    The terminal UI could not be started")]
+struct Args {
+    #[clap(subcommand)]
+    command: Option<Command>,
+
+    /// Settings file to use [default: simulation.toml]
+    #[clap(long, global = true, value_name = "PATH")]
+    config: Option<String>,
+
+    /// Event folder to load, overriding the settings file. Can be given multiple times
+    #[clap(long = "events", global = true, value_name = "DIR", multiple_occurrences = true)]
+    events: Vec<String>,
+
+    /// District folder to load, overriding the settings file. Can be given multiple times
+    #[clap(long = "districts", global = true, value_name = "DIR", multiple_occurrences = true)]
+    districts: Vec<String>,
+
+    /// Make up a cast with this many districts instead of loading the district folders
+    #[clap(long, global = true, value_name = "DISTRICTS")]
+    random_cast: Option<u32>,
+
+    /// Number of tributes in each made up district [default: 2]
+    #[clap(long, global = true, value_name = "N", requires = "random-cast")]
+    tributes_per_district: Option<u32>,
+
+    /// File of names for the made up cast, one per line. Can be given multiple times
+    #[clap(long = "names", global = true, value_name = "FILE", multiple_occurrences = true, requires = "random-cast")]
+    names: Vec<String>,
+
+    /// Seed for the random number generator, for replaying a game
+    #[clap(long, global = true, value_parser = clap::value_parser!(u64).range(..=MAX_SEED))]
+    seed: Option<u64>,
+
+    /// Death rate between 0.0 and 1.0
+    #[clap(long, global = true, value_parser = parse_death_rate)]
+    death_rate: Option<f64>,
+
+    /// Output format for `export` and `batch`: text, json or html
+    #[clap(long, global = true, value_name = "FORMAT")]
+    output: Option<ExportFormat>,
+
+    /// Skip event and district files that can't be loaded, instead of stopping
+    #[clap(long, global = true)]
+    lenient: bool,
+
+    /// List how each fallen tribute died
+    #[clap(long, global = true)]
+    death_causes: bool,
+
+    /// Don't colour the terminal output. Setting NO_COLOR does the same
+    #[clap(long, global = true)]
+    plain: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Open the graphical interface (the default)
+    Gui,
+    /// Run a game in the terminal
+    Run {
+        /// Don't wait for input between phases
+        #[clap(long)]
+        auto: bool,
+    },
+    /// Run a game in a full-screen terminal UI
+    Tui,
+    /// Run many games in a row and report who won them
+    Batch {
+        /// Number of games to run
+        #[clap(long, short = 'n', default_value_t = 100)]
+        games: u32,
+    },
+    /// Load the settings, events and districts and report any problems
+    Validate,
+    /// Run a full game and write out the log
+    Export {
+        /// File to write to. Prints to stdout if not given
+        #[clap(long, short = 'f', value_name = "FILE")]
+        file: Option<String>,
+    },
+    /// Convert an event list and/or cast from BrantSteele's simulator
+    ImportBrantsteele {
+        /// Event list to convert into an event pack
+        #[clap(long, value_name = "FILE")]
+        event_list: Option<String>,
+        /// Cast to convert into district files
+        #[clap(long, value_name = "FILE")]
+        cast: Option<String>,
+        /// Folder to write the converted files to
+        #[clap(long, value_name = "DIR", default_value = "imported")]
+        dir: String,
+    },
+    /// Convert a cast from a CSV file with name, district, pronouns and avatar columns
+    ImportCsv {
+        /// CSV file to convert into district files
+        #[clap(value_name = "FILE")]
+        file: String,
+        /// Folder to write the converted files to
+        #[clap(long, value_name = "DIR", default_value = "imported")]
+        dir: String,
+    },
+}
 
 fn main() {
-    let args: Vec<String> = std::env::args().collect();
-    
-    let settings = match fetch_or_create() {
+    // `-cli` is what we used before subcommands were a thing
+    let args = Args::parse_from(std::env::args().map(|arg|
+        if arg == "-cli" { String::from("run") } else { arg }
+    ));
+
+    let settings = match load_settings(&args) {
         Ok(config) => config,
         Err(err) => {
             eprintln!("An error occurred while loading simulation settings: {}", err);
-            std::process::exit(1)
+            std::process::exit(EXIT_SETTINGS_ERROR)
         }
     };
-    
-    let simulation = match build_sim(settings) {
+
+    let command = args.command.unwrap_or(Command::Gui);
+
+    if let Command::Gui = command {
+        // the GUI reports loading errors itself
+        gui::run(settings, args.config.as_deref().unwrap_or(SETTINGS_FILE));
+        return;
+    }
+
+    if let Command::ImportBrantsteele { event_list, cast, dir } = &command {
+        if event_list.is_none() && cast.is_none() {
+            eprintln!("Nothing to import, give an --event-list and/or a --cast");
+            std::process::exit(EXIT_ARGS_ERROR)
+        }
+
+        if let Err(err) = import::import_brantsteele(event_list.as_deref(), cast.as_deref(), dir) {
+            eprintln!("An error occurred while importing: {}", err);
+            std::process::exit(EXIT_WRITE_ERROR)
+        }
+
+        return;
+    }
+
+    if let Command::ImportCsv { file, dir } = &command {
+        if let Err(err) = import::import_csv(file, dir) {
+            eprintln!("An error occurred while importing: {}", err);
+            std::process::exit(EXIT_WRITE_ERROR)
+        }
+
+        return;
+    }
+
+    let mut simulation = match build_sim(settings) {
         Ok(sim) => sim,
         Err(err) => {
             eprintln!("An error occurred while building simulation: {}", err);
-            std::process::exit(1)
+            std::process::exit(EXIT_LOAD_ERROR)
         }
     };
 
-    if args.contains(&"-cli".to_string()) {
-        run_cli(simulation);
-    } else {
-        gui::run(simulation);
+    let load_errors = simulation.get_load_errors();
+    if !load_errors.is_empty() {
+        eprintln!("Skipped files because of {} error(s) while loading:", load_errors.len());
+
+        for err in load_errors {
+            eprintln!("{}", err);
+        }
     }
-}
 
+    match command {
+        Command::Gui | Command::ImportBrantsteele { .. } | Command::ImportCsv { .. } => (),
+        Command::Run { auto } => {
+            if let Err(err) = cli::run_cli(simulation, auto, style::CliStyle::from_env(args.plain)) {
+                eprintln!("An error occurred while running simulation: {}", err);
+                std::process::exit(EXIT_SIMULATION_ERROR)
+            }
+        },
+        Command::Tui => {
+            if let Err(err) = tui::run(simulation) {
+                eprintln!("An error occurred while running the terminal UI: {}", err);
+                std::process::exit(EXIT_TERMINAL_ERROR)
+            }
+        },
+        Command::Batch { games } => {
+            let format = args.output.unwrap_or(ExportFormat::Text);
 
-fn run_cli(mut simulation: Simulation) {
-    loop {
-        println!("===== {}", simulation.get_category_title());
-        let is_end = simulation.is_end();
+            match batch::run_batch(simulation, games, args.seed) {
+                Ok(stats) => println!("{}", stats.to_string(format)),
+                Err(err) => {
+                    eprintln!("An error occurred while running simulation: {}", err);
+                    std::process::exit(EXIT_SIMULATION_ERROR)
+                }
+            }
+        },
+        Command::Validate => {
+            let errors = simulation.validate_events();
 
-        if let Err(err) = simulation.step() {
-            eprintln!("An error occurred while running simulation: {}", err);
-            std::process::exit(1)
-        }
+            for err in &errors {
+                eprintln!("{}", err);
+            }
+
+            if !errors.is_empty() {
+                eprintln!("{} invalid event(s) found", errors.len());
+            }
 
-        if is_end {
-            if let Some(winner) = simulation.get_winner() {
-                println!("The winner is {} from {}!", winner.name, simulation.get_trib_dist_name(winner));
-            } else {
-                println!("There were no winners this round! (??? Shouldn't happen!! Report if it does)");
+            // in lenient mode the files that couldn't be loaded
+            // were already listed above
+            if !errors.is_empty() || !simulation.get_load_errors().is_empty() {
+                std::process::exit(EXIT_LOAD_ERROR)
             }
-        } else {
-            for event in &simulation.get_clear_next_events() {
-                println!("{}", event.text)
+
+            println!("Everything looks good! {} events and {} districts loaded", simulation.get_all_events().len(), simulation.get_districts().len());
+        },
+        Command::Export { file } => {
+            let format = args.output.unwrap_or_else(||
+                file.as_deref().map(ExportFormat::from_path).unwrap_or(ExportFormat::Text)
+            );
+
+            if let Err(err) = cli::run_to_end(&mut simulation) {
+                eprintln!("An error occurred while running simulation: {}", err);
+                std::process::exit(EXIT_SIMULATION_ERROR)
+            }
+
+            let contents = match export(&simulation, format) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    eprintln!("An error occurred while exporting the game log: {}", err);
+                    std::process::exit(EXIT_WRITE_ERROR)
+                }
+            };
+
+            match file {
+                Some(path) => if let Err(err) = std::fs::write(&path, contents) {
+                    eprintln!("Could not write file `{}`: {}", path, err);
+                    std::process::exit(EXIT_WRITE_ERROR)
+                },
+                None => print!("{}", contents)
             }
         }
-    
-        println!("\nPress enter to continue");
-        let mut pebis = String::new();
-    
-        std::io::stdin()
-            .read_line(&mut pebis)
-            .expect("Failed to read line!");
     }
-}
\ No newline at end of file
+}
+
+// Loads the settings file, then applies any overrides from the command line
+fn load_settings(args: &Args) -> Result<SimulationSettings, hg::data_trait::FileError> {
+    let mut settings = match &args.config {
+        Some(path) => SimulationSettings::parse(path)?,
+        None => fetch_or_create()?
+    };
+
+    if !args.events.is_empty() {
+        settings.event_folders = args.events.clone();
+    }
+
+    if !args.districts.is_empty() {
+        settings.district_folders = args.districts.clone();
+        settings.random_cast = None;
+    }
+
+    if let Some(districts) = args.random_cast {
+        // anything else about the cast comes from the settings file, if it has one
+        let cast = settings.random_cast.get_or_insert_with(|| CastSettings::new(districts, 2));
+        cast.districts = districts;
+
+        if let Some(tributes) = args.tributes_per_district {
+            cast.tributes_per_district = tributes;
+        }
+
+        if !args.names.is_empty() {
+            cast.name_lists = args.names.clone();
+        }
+    }
+
+    if args.seed.is_some() {
+        settings.seed = args.seed;
+    }
+
+    if args.lenient {
+        settings.lenient = true;
+    }
+
+    if args.death_causes {
+        settings.show_death_causes = true;
+    }
+
+    if let Some(death_rate) = args.death_rate {
+        settings.death_rate = death_rate;
+    }
+
+    Ok(settings)
+}
+
+fn parse_death_rate(arg: &str) -> Result<f64, String> {
+    let death_rate = arg.parse::<f64>().map_err(|err| err.to_string())?;
+
+    if (0.0..=1.0).contains(&death_rate) {
+        Ok(death_rate)
+    } else {
+        Err(format!("{} is not between 0.0 and 1.0", arg))
+    }
+}
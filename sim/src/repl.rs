@@ -0,0 +1,97 @@
+use std::io::Write;
+
+use hg::{simulation::Simulation, export::{save_log, export_text}};
+
+// What to do once the user's done poking at the current phase
+pub enum ReplAction {
+    // run this many phases before asking again
+    Continue(u32),
+    Auto,
+    Quit
+}
+
+const HELP: &str = "Commands:
+    (enter)          continue to the next phase
+    status           list the living tributes
+    board            show the status board
+    tribute <name>   show everything that happened to a tribute
+    skip <n>         run the next n phases without stopping
+    auto             run the rest of the game without stopping
+    save <file>      save the game log so far (.txt, .json or .html)
+    log              print the game log so far
+    quit             stop the simulation
+    help             show this message";
+
+pub fn prompt(simulation: &Simulation) -> ReplAction {
+    println!("\nPress enter to continue, or type \"help\" for a list of commands");
+
+    loop {
+        print!("> ");
+        let _ = std::io::stdout().flush();
+
+        let mut pebis = String::new();
+
+        // once stdin runs dry, we just keep going
+        if std::io::stdin().read_line(&mut pebis).unwrap_or(0) == 0 {
+            return ReplAction::Continue(1)
+        }
+
+        let line = pebis.trim();
+        let (command, arg) = match line.split_once(' ') {
+            Some((command, arg)) => (command, arg.trim()),
+            None => (line, "")
+        };
+
+        match command.to_lowercase().as_str() {
+            "" => return ReplAction::Continue(1),
+            "status" => println!("{}", simulation.cli_display_living()),
+            "board" => println!("{}", simulation.cli_display_status()),
+            "tribute" => println!("{}", tribute_history(simulation, arg)),
+            "skip" => match arg.parse::<u32>() {
+                Ok(phases) if phases > 0 => return ReplAction::Continue(phases),
+                _ => println!("Usage: skip <number of phases>")
+            },
+            "auto" => return ReplAction::Auto,
+            "save" => {
+                if arg.is_empty() {
+                    println!("Usage: save <file>");
+                } else {
+                    match save_log(simulation, arg) {
+                        Ok(()) => println!("Saved game log to {}", arg),
+                        Err(err) => println!("{}", err)
+                    }
+                }
+            },
+            "log" => println!("{}", export_text(simulation)),
+            "quit" | "exit" => return ReplAction::Quit,
+            "help" => println!("{}", HELP),
+            _ => println!("Unknown command `{}`, type \"help\" for a list of commands", command)
+        }
+    }
+}
+
+fn tribute_history(simulation: &Simulation, name: &str) -> String {
+    if name.is_empty() {
+        return String::from("Usage: tribute <name>")
+    }
+
+    let trib = match simulation.get_tribute_by_name(name) {
+        Some(trib) => trib,
+        None => return format!("There's no tribute named `{}`", name)
+    };
+
+    let state = if trib.is_alive { "Alive" } else { "Deceased" };
+    let kill_txt = if trib.kills == 1 { "kill" } else { "kills" };
+
+    let mut text = format!("{} ({}) - {} [{} {}]\n", trib.name, simulation.get_trib_dist_name(trib), state, trib.kills, kill_txt);
+
+    for entry in simulation.get_timeline(trib.get_id()) {
+        text.push_str(&format!("\t{}: {}\n", entry.title, entry.text));
+
+        for fact in &entry.facts {
+            text.push_str(&format!("\t\t- {}\n", fact.get_text(simulation)));
+        }
+    }
+
+    text
+}
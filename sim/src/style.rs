@@ -0,0 +1,79 @@
+use std::io::IsTerminal;
+
+use hg::event::{EventCategory, EventResult, TextSpan};
+
+const RESET: &str = "\x1b[0m";
+const BOLD: &str = "\x1b[1m";
+const RED: &str = "\x1b[1;31m";
+const GREEN: &str = "\x1b[1;32m";
+const YELLOW: &str = "\x1b[1;33m";
+const CYAN: &str = "\x1b[1;36m";
+
+// Formats CLI output, with or without ANSI colours
+pub struct CliStyle {
+    color: bool
+}
+
+impl CliStyle {
+    // Colours are used unless `--plain` was given, `NO_COLOR` is set
+    // (see https://no-color.org) or stdout isn't a terminal
+    pub fn from_env(plain: bool) -> Self {
+        let no_color = std::env::var_os("NO_COLOR").map(|v| !v.is_empty()).unwrap_or(false);
+
+        Self {
+            color: !plain && !no_color && std::io::stdout().is_terminal()
+        }
+    }
+
+    fn paint(&self, text: &str, code: &str) -> String {
+        if self.color {
+            format!("{}{}{}", code, text, RESET)
+        } else {
+            text.to_string()
+        }
+    }
+
+    pub fn title(&self, title: &str) -> String {
+        self.paint(&format!("===== {}", title), BOLD)
+    }
+
+    pub fn victory(&self, text: &str) -> String {
+        self.paint(text, GREEN)
+    }
+
+    // Highlights every tribute in the event: the dead in red, their
+    // killers in yellow and everyone else in cyan. Fallen tributes get indented
+    pub fn event(&self, event: &EventResult, category: &EventCategory) -> String {
+        if *category == EventCategory::FallenTributes && !event.tributes.is_empty() {
+            return format!("\t{}", self.highlight(event, RED));
+        }
+
+        self.highlight(event, CYAN)
+    }
+
+    fn highlight(&self, event: &EventResult, default_code: &str) -> String {
+        if !self.color {
+            return event.text.trim_end().to_string()
+        }
+
+        let text = event.spans
+            .iter()
+            .map(|span| match span {
+                TextSpan::Tribute { id, name } => {
+                    let code = if event.killed.contains(id) {
+                        RED
+                    } else if event.killers.contains(id) {
+                        YELLOW
+                    } else {
+                        default_code
+                    };
+
+                    self.paint(name, code)
+                },
+                span => span.get_text().to_string()
+            })
+            .collect::<String>();
+
+        text.trim_end().to_string()
+    }
+}
@@ -0,0 +1,116 @@
+use crossterm::event::KeyCode;
+use hg::{simulation::{Simulation, SimulationError}, export::save_log};
+
+pub struct TuiApp {
+    pub simulation: Simulation,
+    // index into the simulation's history. One past the
+    // last phase is the summary, once the game is over
+    pub page: usize,
+    pub scroll: u16,
+    pub show_status_board: bool,
+    // shown in the footer until the next key press
+    pub status: Option<String>,
+    // the event that made the last step fail, so it can be skipped
+    pub failed_event: Option<String>,
+    pub should_quit: bool
+}
+
+impl TuiApp {
+    pub fn new(simulation: Simulation) -> Self {
+        let mut app = Self {
+            simulation,
+            page: 0,
+            scroll: 0,
+            show_status_board: true,
+            status: None,
+            failed_event: None,
+            should_quit: false
+        };
+
+        app.step();
+
+        app
+    }
+
+    // e.g. if the first step failed, there's nothing to show yet
+    pub fn is_summary(&self) -> bool {
+        self.simulation.is_end() && self.page >= self.simulation.get_history().len()
+    }
+
+    pub fn handle_key(&mut self, key: KeyCode) {
+        self.status = None;
+
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.should_quit = true,
+            KeyCode::Char('n') | KeyCode::Char(' ') | KeyCode::Right | KeyCode::Enter => self.forward(),
+            KeyCode::Char('p') | KeyCode::Left => self.back(),
+            KeyCode::Down | KeyCode::Char('j') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::PageDown => self.scroll = self.scroll.saturating_add(10),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(10),
+            KeyCode::Char('b') => self.show_status_board = !self.show_status_board,
+            KeyCode::Char('s') => self.save(),
+            KeyCode::Char('x') => self.skip_failed_event(),
+            _ => ()
+        }
+    }
+
+    fn forward(&mut self) {
+        let last_page = self.simulation.get_history().len().saturating_sub(1);
+
+        if self.page < last_page {
+            self.page += 1;
+        } else if self.page == last_page {
+            if self.simulation.is_end() {
+                // on to the summary
+                self.page += 1;
+            } else {
+                self.step();
+            }
+        }
+
+        self.scroll = 0;
+    }
+
+    fn back(&mut self) {
+        self.page = self.page.saturating_sub(1);
+        self.scroll = 0;
+    }
+
+    fn step(&mut self) {
+        match self.simulation.step() {
+            Ok(()) => {
+                self.simulation.get_clear_next_events();
+                self.page = self.simulation.get_history().len().saturating_sub(1);
+                self.failed_event = None;
+            },
+            Err(err) => {
+                self.failed_event = match &err {
+                    SimulationError::EventError { file, .. } => Some(file.clone()),
+                    _ => None
+                };
+
+                self.status = Some(match &self.failed_event {
+                    Some(_) => format!("{} (press x to skip this event)", err),
+                    None => format!("{}", err)
+                });
+            }
+        }
+    }
+
+    fn skip_failed_event(&mut self) {
+        if let Some(file) = self.failed_event.take() {
+            self.simulation.skip_event(&file);
+            self.step();
+        }
+    }
+
+    fn save(&mut self) {
+        let path = format!("hg_sim_{}.txt", self.simulation.get_seed());
+
+        self.status = Some(match save_log(&self.simulation, &path) {
+            Ok(()) => format!("Saved game log to {}", path),
+            Err(err) => format!("{}", err)
+        });
+    }
+}
@@ -0,0 +1,60 @@
+use std::io;
+
+use crossterm::{
+    event::{self, Event, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use hg::simulation::Simulation;
+use ratatui::{backend::CrosstermBackend, Terminal};
+
+pub mod app;
+pub mod ui;
+
+// Full-screen terminal alternative to `gui::run`, for when there's no
+// display to open a window on (e.g. over SSH)
+pub fn run(simulation: Simulation) -> Result<(), io::Error> {
+    // a panic would otherwise leave the terminal in raw mode on the
+    // alternate screen, with the message lost along with it
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout))?;
+
+    let result = run_app(&mut terminal, app::TuiApp::new(simulation));
+
+    // put the terminal back the way we found it, even if something went wrong
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+
+    result
+}
+
+// Best effort, since it's only used when things have already gone wrong
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show);
+}
+
+fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, mut app: app::TuiApp) -> Result<(), io::Error> {
+    while !app.should_quit {
+        terminal.draw(|frame| ui::draw(frame, &app))?;
+
+        if let Event::Key(key) = event::read()? {
+            // some platforms also report key releases
+            if key.kind == KeyEventKind::Press {
+                app.handle_key(key.code);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,143 @@
+use hg::event::{EventCategory, EventResult};
+use ratatui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans, Text},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::app::TuiApp;
+
+const KEYS: &str = "[n/→] next  [p/←] back  [↑/↓] scroll  [b] status board  [s] save log  [q] quit";
+
+pub fn draw<B: Backend>(frame: &mut Frame<B>, app: &TuiApp) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3),
+            Constraint::Min(1),
+            Constraint::Length(3)
+        ])
+        .split(frame.size());
+
+    draw_header(frame, app, rows[0]);
+
+    if app.show_status_board {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(65),
+                Constraint::Percentage(35)
+            ])
+            .split(rows[1]);
+
+        draw_events(frame, app, columns[0]);
+        draw_status_board(frame, app, columns[1]);
+    } else {
+        draw_events(frame, app, rows[1]);
+    }
+
+    draw_footer(frame, app, rows[2]);
+}
+
+fn draw_header<B: Backend>(frame: &mut Frame<B>, app: &TuiApp, area: Rect) {
+    let title = match app.simulation.get_history().get(app.page) {
+        Some(phase) => phase.title.clone(),
+        None => app.simulation.get_category_title()
+    };
+
+    let header = Paragraph::new(Spans::from(vec![
+        Span::styled(title, Style::default().add_modifier(Modifier::BOLD)),
+        Span::raw(format!("   (seed {})", app.simulation.get_seed()))
+    ]))
+    .block(Block::default().borders(Borders::ALL).title("Hunger Games Simulator"));
+
+    frame.render_widget(header, area);
+}
+
+fn draw_events<B: Backend>(frame: &mut Frame<B>, app: &TuiApp, area: Rect) {
+    let text = if app.is_summary() {
+        let mut text = Text::default();
+
+        if let Some(victory) = app.simulation.get_victory_text() {
+            text.extend(Text::styled(victory, Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)));
+            text.extend(Text::raw(""));
+        }
+
+        text.extend(Text::raw(app.simulation.get_summary().to_text()));
+
+        text
+    } else {
+        // nothing's happened yet if the first phase failed
+        let lines = match app.simulation.get_history().get(app.page) {
+            Some(phase) => phase.events
+                .iter()
+                .map(|event| event_line(event, &phase.category))
+                .collect::<Vec<Spans>>(),
+            None => Vec::new()
+        };
+
+        Text::from(lines)
+    };
+
+    let events = Paragraph::new(text)
+        .block(Block::default().borders(Borders::ALL).title("Events"))
+        .wrap(Wrap { trim: false })
+        .scroll((app.scroll, 0));
+
+    frame.render_widget(events, area);
+}
+
+// Deaths get coloured, everything else is left alone
+fn event_line<'a>(event: &'a EventResult, category: &EventCategory) -> Spans<'a> {
+    let text = event.text.trim_end();
+
+    if *category == EventCategory::FallenTributes && !event.tributes.is_empty() {
+        Spans::from(Span::styled(format!("  {}", text), Style::default().fg(Color::Red)))
+    } else if !event.killed.is_empty() {
+        Spans::from(Span::styled(text, Style::default().fg(Color::Red)))
+    } else {
+        Spans::from(Span::raw(text))
+    }
+}
+
+fn draw_status_board<B: Backend>(frame: &mut Frame<B>, app: &TuiApp, area: Rect) {
+    let mut lines = Vec::new();
+
+    for dist in app.simulation.get_status_board() {
+        lines.push(Spans::from(Span::styled(dist.name.clone(), Style::default().add_modifier(Modifier::BOLD))));
+
+        for trib in &dist.tributes {
+            let style = if trib.is_alive {
+                Style::default().fg(Color::Green)
+            } else {
+                Style::default().fg(Color::Red).add_modifier(Modifier::CROSSED_OUT)
+            };
+
+            lines.push(Spans::from(vec![
+                Span::raw("  "),
+                Span::styled(trib.name.clone(), style),
+                Span::raw(format!(" [{}]", trib.get_kills_text()))
+            ]));
+        }
+
+        lines.push(Spans::from(""));
+    }
+
+    let board = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title("Status Board"))
+        .wrap(Wrap { trim: false });
+
+    frame.render_widget(board, area);
+}
+
+fn draw_footer<B: Backend>(frame: &mut Frame<B>, app: &TuiApp, area: Rect) {
+    let footer = match &app.status {
+        Some(status) => Paragraph::new(Span::styled(status.clone(), Style::default().fg(Color::Yellow))),
+        None => Paragraph::new(KEYS)
+    };
+
+    frame.render_widget(footer.block(Block::default().borders(Borders::ALL)), area);
+}